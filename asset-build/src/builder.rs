@@ -0,0 +1,420 @@
+use convert_case::{Case, Casing};
+use proc_macro2::Span;
+use quote::ToTokens;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use syn::parse::Parser;
+use syn::{Attribute, Ident, LitBool, LitStr, Visibility};
+
+use crate::ir::AssetEnum;
+use crate::parse::AssetsInput;
+
+/// Error returned by [`AssetBuilder::write_to`].
+#[derive(Debug)]
+pub enum Error {
+    /// The options are invalid, or the asset directory does not match them, e.g. because it
+    /// contains no matching files.
+    Invalid(String),
+    /// Writing the generated file failed.
+    Io(io::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Invalid(message) => f.write_str(message),
+            Error::Io(error) => write!(f, "Failed to write generated assets: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Invalid(_) => None,
+            Error::Io(error) => Some(error),
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Error::Io(error)
+    }
+}
+
+impl From<syn::Error> for Error {
+    fn from(error: syn::Error) -> Self {
+        Error::Invalid(error.to_string())
+    }
+}
+
+/// Generates the same code as the `assets!` macro from a build script.
+///
+/// The options correspond to the parameters of `assets!` and take the same values. Unlike the
+/// macro, the builder can embed files that the build script generates itself, e.g. in
+/// `OUT_DIR`:
+///
+/// ```no_run
+/// // build.rs
+/// use asset_build::AssetBuilder;
+///
+/// let out_dir = std::env::var("OUT_DIR").unwrap();
+/// AssetBuilder::new(format!("{}/ui", out_dir))
+///     .enum_name("UiAssets")
+///     .include(r"\.(png|svg)$")
+///     .write_to(&out_dir)
+///     .unwrap();
+/// ```
+///
+/// The generated file is named after the enum in snake_case and is included in the crate with
+/// `include!(concat!(env!("OUT_DIR"), "/ui_assets.rs"));`. The crate has to depend on
+/// `asset-traits`, like it does when using the macro.
+#[derive(Debug, Clone)]
+pub struct AssetBuilder {
+    dirs: Vec<PathBuf>,
+    attributes: Vec<String>,
+    visibility: Option<String>,
+    enum_name: String,
+    include: Option<String>,
+    ignore: Option<String>,
+    include_globs: Vec<String>,
+    ignore_globs: Vec<String>,
+    respect_gitignore: bool,
+    compress: Option<String>,
+    hash: Option<String>,
+    mime_overrides: Vec<(String, String)>,
+    sniff_mime: bool,
+    layout: Option<String>,
+    dev_reload: bool,
+    text: Option<String>,
+    collision: Option<String>,
+    renames: Vec<(String, String)>,
+    aliases: Vec<(String, String)>,
+    serde: bool,
+    clap: bool,
+}
+
+impl AssetBuilder {
+    /// Create a builder for the assets in `dir`.
+    ///
+    /// `$VAR` and `${VAR}` in `dir` are replaced with environment variables, and relative paths
+    /// are resolved against `CARGO_MANIFEST_DIR`. The generated enum is called
+    /// `Assets` unless [`enum_name`](Self::enum_name) is set.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dirs: vec![dir.into()],
+            attributes: Vec::new(),
+            visibility: None,
+            enum_name: "Assets".to_string(),
+            include: None,
+            ignore: None,
+            include_globs: Vec::new(),
+            ignore_globs: Vec::new(),
+            respect_gitignore: false,
+            compress: None,
+            hash: None,
+            mime_overrides: Vec::new(),
+            sniff_mime: false,
+            layout: None,
+            dev_reload: false,
+            text: None,
+            collision: None,
+            renames: Vec::new(),
+            aliases: Vec::new(),
+            serde: false,
+            clap: false,
+        }
+    }
+
+    /// Add another asset root. Its files replace those with the same relative path in the
+    /// directories added before it.
+    pub fn dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.dirs.push(dir.into());
+        self
+    }
+
+    /// Set the name of the generated enum, or of the module for the nested layout.
+    pub fn enum_name(mut self, name: impl Into<String>) -> Self {
+        self.enum_name = name.into();
+        self
+    }
+
+    /// Add an outer attribute to the generated enum, e.g. `#[derive(PartialOrd, Ord)]`. Can be
+    /// called several times.
+    pub fn attribute(mut self, attribute: impl Into<String>) -> Self {
+        self.attributes.push(attribute.into());
+        self
+    }
+
+    /// Set the visibility of the generated enum, e.g. `pub(crate)`. Defaults to `pub`.
+    pub fn visibility(mut self, visibility: impl Into<String>) -> Self {
+        self.visibility = Some(visibility.into());
+        self
+    }
+
+    /// Only embed files whose full path matches the regex `pattern`.
+    pub fn include(mut self, pattern: impl Into<String>) -> Self {
+        self.include = Some(pattern.into());
+        self
+    }
+
+    /// Skip files and directories whose full path matches the regex `pattern`.
+    pub fn ignore(mut self, pattern: impl Into<String>) -> Self {
+        self.ignore = Some(pattern.into());
+        self
+    }
+
+    /// Embed files whose relative path matches the glob `pattern`. Can be called several times.
+    pub fn include_glob(mut self, pattern: impl Into<String>) -> Self {
+        self.include_globs.push(pattern.into());
+        self
+    }
+
+    /// Skip files and directories whose relative path matches the glob `pattern`. Can be called
+    /// several times.
+    pub fn ignore_glob(mut self, pattern: impl Into<String>) -> Self {
+        self.ignore_globs.push(pattern.into());
+        self
+    }
+
+    /// Skip files ignored by `.gitignore` files.
+    pub fn respect_gitignore(mut self, respect: bool) -> Self {
+        self.respect_gitignore = respect;
+        self
+    }
+
+    /// Store the assets compressed with `"gzip"`, `"brotli"` or `"zstd"`.
+    pub fn compress(mut self, algorithm: impl Into<String>) -> Self {
+        self.compress = Some(algorithm.into());
+        self
+    }
+
+    /// Set the hash algorithm, `"sha256"` (default) or `"blake3"`.
+    pub fn hash(mut self, algorithm: impl Into<String>) -> Self {
+        self.hash = Some(algorithm.into());
+        self
+    }
+
+    /// Use `mime_type` for files with the extension `ext`.
+    pub fn mime_override(mut self, ext: impl Into<String>, mime_type: impl Into<String>) -> Self {
+        self.mime_overrides.push((ext.into(), mime_type.into()));
+        self
+    }
+
+    /// Detect the MIME type of files with unknown extensions from their magic bytes.
+    pub fn sniff_mime(mut self, sniff: bool) -> Self {
+        self.sniff_mime = sniff;
+        self
+    }
+
+    /// Set the layout of the generated code, `"flat"` (default) or `"nested"`.
+    pub fn layout(mut self, layout: impl Into<String>) -> Self {
+        self.layout = Some(layout.into());
+        self
+    }
+
+    /// Read the files from disk at runtime in debug builds.
+    pub fn dev_reload(mut self, dev_reload: bool) -> Self {
+        self.dev_reload = dev_reload;
+        self
+    }
+
+    /// Validate files whose relative path matches the regex `pattern` as UTF-8.
+    pub fn text(mut self, pattern: impl Into<String>) -> Self {
+        self.text = Some(pattern.into());
+        self
+    }
+
+    /// Set the policy for clashing identifiers, `"error"` (default) or `"suffix"`.
+    pub fn collision(mut self, policy: impl Into<String>) -> Self {
+        self.collision = Some(policy.into());
+        self
+    }
+
+    /// Name the variant of the file at the relative path `path` `variant`.
+    pub fn rename(mut self, path: impl Into<String>, variant: impl Into<String>) -> Self {
+        self.renames.push((path.into(), variant.into()));
+        self
+    }
+
+    /// Make `find_by_path` resolve the additional path `path` to `variant`.
+    pub fn alias(mut self, path: impl Into<String>, variant: impl Into<String>) -> Self {
+        self.aliases.push((path.into(), variant.into()));
+        self
+    }
+
+    /// Implement `Serialize` and `Deserialize` as the asset path. Requires the `serde` feature
+    /// of `asset-traits`.
+    pub fn serde(mut self, serde: bool) -> Self {
+        self.serde = serde;
+        self
+    }
+
+    /// Implement `clap::ValueEnum` with the asset paths as possible values. Requires the `clap`
+    /// feature of `asset-traits`.
+    pub fn clap(mut self, clap: bool) -> Self {
+        self.clap = clap;
+        self
+    }
+
+    /// Generate the code and write it to `out_dir`, returning the path of the written file.
+    ///
+    /// Also tells Cargo to rerun the build script when an embedded file, an ignore file, the
+    /// list of files in a scanned directory or an environment variable used in the path changes.
+    pub fn write_to(&self, out_dir: impl AsRef<Path>) -> Result<PathBuf, Error> {
+        let assets = AssetEnum::try_from(self.to_input()?)?;
+
+        let file = syn::parse2(assets.to_token_stream())?;
+        let path = out_dir
+            .as_ref()
+            .join(format!("{}.rs", self.enum_name.to_case(Case::Snake)));
+        fs::write(&path, prettyplease::unparse(&file))?;
+
+        for tracked in assets.scanned_dirs().iter().chain(assets.tracked_files()) {
+            println!("cargo:rerun-if-changed={}", tracked);
+        }
+        for var in assets.env_vars() {
+            println!("cargo:rerun-if-env-changed={}", var);
+        }
+
+        Ok(path)
+    }
+
+    /// Convert the options to the input of the macro.
+    fn to_input(&self) -> Result<AssetsInput, Error> {
+        let lit = |value: &str| LitStr::new(value, Span::call_site());
+        let lit_opt = |value: &Option<String>| value.as_deref().map(lit);
+        let lit_bool = |value: bool| Some(LitBool::new(value, Span::call_site()));
+        let ident = |value: &str| {
+            syn::parse_str::<Ident>(value)
+                .map_err(|_| Error::Invalid(format!("'{}' is not a valid identifier", value)))
+        };
+        let ident_opt = |value: &Option<String>| value.as_deref().map(ident).transpose();
+        let ident_map = |map: &[(String, String)]| {
+            map.iter()
+                .map(|(path, variant)| Ok((lit(path), ident(variant)?)))
+                .collect::<Result<Vec<_>, Error>>()
+        };
+
+        let attrs = self
+            .attributes
+            .iter()
+            .map(|attribute| {
+                Attribute::parse_outer.parse_str(attribute).map_err(|_| {
+                    Error::Invalid(format!("'{}' is not a valid attribute", attribute))
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let vis = match &self.visibility {
+            Some(visibility) => syn::parse_str(visibility).map_err(|_| {
+                Error::Invalid(format!("'{}' is not a valid visibility", visibility))
+            })?,
+            None => Visibility::Inherited,
+        };
+
+        Ok(AssetsInput {
+            attrs: attrs.into_iter().flatten().collect(),
+            vis,
+            enum_name: ident(&self.enum_name)?,
+            dir_path_lits: self
+                .dirs
+                .iter()
+                .map(|dir| lit(&dir.to_string_lossy()))
+                .collect(),
+            include_pattern_lit: lit_opt(&self.include),
+            ignore_pattern_lit: lit_opt(&self.ignore),
+            compress_lit: lit_opt(&self.compress),
+            hash_lit: lit_opt(&self.hash),
+            mime_overrides: self
+                .mime_overrides
+                .iter()
+                .map(|(ext, mime_type)| (lit(ext), lit(mime_type)))
+                .collect(),
+            sniff_mime_lit: lit_bool(self.sniff_mime),
+            layout_ident: ident_opt(&self.layout)?,
+            dev_reload_lit: lit_bool(self.dev_reload),
+            text_pattern_lit: lit_opt(&self.text),
+            collision_ident: ident_opt(&self.collision)?,
+            renames: ident_map(&self.renames)?,
+            aliases: ident_map(&self.aliases)?,
+            include_globs: self.include_globs.iter().map(|glob| lit(glob)).collect(),
+            ignore_globs: self.ignore_globs.iter().map(|glob| lit(glob)).collect(),
+            respect_gitignore_lit: lit_bool(self.respect_gitignore),
+            serde_lit: lit_bool(self.serde),
+            clap_lit: lit_bool(self.clap),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TempDir;
+
+    #[test]
+    fn test_write_to() {
+        let root = TempDir::new("builder");
+        root.write("assets/ui/logo.png", "");
+        root.write("assets/readme.txt", "");
+
+        let path = AssetBuilder::new(root.join("assets"))
+            .enum_name("UiAssets")
+            .attribute("#[derive(PartialOrd, Ord)]")
+            .visibility("pub(crate)")
+            .include_glob("**/*.png")
+            .rename("ui/logo.png", "Logo")
+            .write_to(&root)
+            .unwrap();
+        assert_eq!(path, root.join("ui_assets.rs"));
+
+        let code = fs::read_to_string(&path).unwrap();
+        assert!(code.contains("#[derive(PartialOrd, Ord)]\npub(crate) enum UiAssets {\n"));
+        assert!(code.contains("    /// `ui/logo.png`\n"));
+        assert!(code.contains("    Logo,\n}"));
+        assert!(!code.contains("readme.txt"));
+
+        // Renamed files keep their names in the nested modules as well.
+        let path = AssetBuilder::new(root.join("assets"))
+            .layout("nested")
+            .rename("ui/logo.png", "Brand")
+            .write_to(&root)
+            .unwrap();
+        let code = fs::read_to_string(&path).unwrap();
+        assert!(code.contains("pub const Brand: File = File::Brand;"));
+
+        let error = AssetBuilder::new(root.join("assets"))
+            .layout("sideways")
+            .write_to(&root)
+            .unwrap_err();
+        assert!(matches!(error, Error::Invalid(message) if message.contains("Unknown layout")));
+
+        let error = AssetBuilder::new(root.join("assets"))
+            .include("(")
+            .write_to(&root)
+            .unwrap_err();
+        assert!(matches!(error, Error::Invalid(message) if message.contains("Invalid regex")));
+    }
+
+    #[test]
+    fn test_override_dirs() {
+        let root = TempDir::new("dirs");
+        root.write("common/logo.png", "");
+        root.write("desktop/logo.png", "");
+        root.write("common/icon.png", "");
+
+        let path = AssetBuilder::new(root.join("common"))
+            .dir(root.join("desktop"))
+            .write_to(&root)
+            .unwrap();
+
+        let code = fs::read_to_string(path).unwrap();
+        assert!(code.contains("pub enum Assets {\n"));
+        assert!(code.contains("    IconPng,\n"));
+        assert!(code.contains("    LogoPng,\n}"));
+        assert!(code.contains("desktop/logo.png"));
+        assert!(!code.contains("common/logo.png"));
+    }
+}
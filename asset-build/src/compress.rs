@@ -0,0 +1,149 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::LitStr;
+
+/// Compression algorithm selected with the `compress` option of the `assets!` macro.
+#[derive(Clone, Copy)]
+pub(crate) enum Compression {
+    Gzip,
+    Brotli,
+    Zstd,
+}
+
+impl Compression {
+    /// Parse the algorithm name, making sure the matching cargo feature is enabled.
+    pub(crate) fn from_lit(lit: &LitStr) -> syn::Result<Self> {
+        let (compression, enabled) = match lit.value().as_str() {
+            "gzip" => (Compression::Gzip, cfg!(feature = "gzip")),
+            "brotli" => (Compression::Brotli, cfg!(feature = "brotli")),
+            "zstd" => (Compression::Zstd, cfg!(feature = "zstd")),
+            other => {
+                return Err(syn::Error::new(
+                    lit.span(),
+                    format!(
+                        "Unknown compression '{}'. Expected 'gzip', 'brotli' or 'zstd'",
+                        other
+                    ),
+                ));
+            }
+        };
+
+        if !enabled {
+            return Err(syn::Error::new(
+                lit.span(),
+                format!(
+                    "Compression '{}' requires the '{}' feature of asset-macros, or of asset-build \
+                     when generating the code from a build script",
+                    lit.value(),
+                    lit.value()
+                ),
+            ));
+        }
+
+        Ok(compression)
+    }
+
+    /// Compress the file contents.
+    #[cfg_attr(
+        not(any(feature = "gzip", feature = "brotli", feature = "zstd")),
+        allow(unused_variables)
+    )]
+    pub(crate) fn compress(self, data: &[u8]) -> std::io::Result<Vec<u8>> {
+        match self {
+            #[cfg(feature = "gzip")]
+            Compression::Gzip => {
+                use std::io::Write;
+
+                let mut encoder =
+                    flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::best());
+                encoder.write_all(data)?;
+                encoder.finish()
+            }
+            #[cfg(feature = "brotli")]
+            Compression::Brotli => {
+                let mut output = Vec::new();
+                let params = brotli::enc::BrotliEncoderParams {
+                    quality: 11,
+                    ..Default::default()
+                };
+                brotli::BrotliCompress(&mut &data[..], &mut output, &params)?;
+                Ok(output)
+            }
+            #[cfg(feature = "zstd")]
+            Compression::Zstd => zstd::stream::encode_all(data, 19),
+            #[allow(unreachable_patterns)]
+            _ => unreachable!("compression feature checked in Compression::from_lit"),
+        }
+    }
+}
+
+/// Compress file contents, keeping them uncompressed when compression does not make them smaller.
+pub(crate) fn compress_data(
+    data: &[u8],
+    compression: Compression,
+) -> std::io::Result<Option<(Compression, Vec<u8>)>> {
+    let compressed = compression.compress(data)?;
+    if compressed.len() < data.len() {
+        Ok(Some((compression, compressed)))
+    } else {
+        Ok(None)
+    }
+}
+
+impl quote::ToTokens for Compression {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        tokens.extend(match self {
+            Compression::Gzip => quote!(asset_traits::Compression::Gzip),
+            Compression::Brotli => quote!(asset_traits::Compression::Brotli),
+            Compression::Zstd => quote!(asset_traits::Compression::Zstd),
+        });
+    }
+}
+
+#[cfg(all(test, any(feature = "gzip", feature = "brotli", feature = "zstd")))]
+mod tests {
+    use super::*;
+
+    /// Compress repetitive data and decompress it the way the generated code does.
+    fn round_trip(compression: Compression, runtime: asset_traits::Compression) {
+        let data = "body { color: #333; }\n".repeat(64).into_bytes();
+        let (_, compressed) = compress_data(&data, compression).unwrap().unwrap();
+        assert!(compressed.len() < data.len());
+        assert_eq!(runtime.decompress(&compressed), data);
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn test_gzip_round_trip() {
+        round_trip(Compression::Gzip, asset_traits::Compression::Gzip);
+    }
+
+    #[cfg(feature = "brotli")]
+    #[test]
+    fn test_brotli_round_trip() {
+        round_trip(Compression::Brotli, asset_traits::Compression::Brotli);
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn test_zstd_round_trip() {
+        round_trip(Compression::Zstd, asset_traits::Compression::Zstd);
+    }
+
+    #[test]
+    fn test_incompressible_data() {
+        let compressions = [
+            #[cfg(feature = "gzip")]
+            Compression::Gzip,
+            #[cfg(feature = "brotli")]
+            Compression::Brotli,
+            #[cfg(feature = "zstd")]
+            Compression::Zstd,
+        ];
+        assert!(
+            compressions
+                .into_iter()
+                .all(|compression| compress_data(b"x", compression).unwrap().is_none())
+        );
+    }
+}
@@ -0,0 +1,267 @@
+use std::collections::HashMap;
+use std::path::Path;
+use syn::{Data, DeriveInput, Fields, LitBool, LitStr};
+
+use crate::compress::Compression;
+use crate::hash::HashAlgorithm;
+use crate::ir::{AssetEnum, EntryLoader};
+use crate::mime::MimeDetector;
+use crate::utils::{expand_env_vars, normalize_rel_path, regex_from_lit};
+
+/// Options of the `#[asset(...)]` attribute on the enum.
+#[derive(Default)]
+struct EnumOptions {
+    dir_lit: Option<LitStr>,
+    compress_lit: Option<LitStr>,
+    hash_lit: Option<LitStr>,
+    text_pattern_lit: Option<LitStr>,
+    dev_reload_lit: Option<LitBool>,
+    sniff_mime_lit: Option<LitBool>,
+}
+
+impl EnumOptions {
+    fn parse(input: &DeriveInput) -> syn::Result<Self> {
+        let mut options = Self::default();
+
+        for attr in input
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("asset"))
+        {
+            attr.parse_nested_meta(|meta| {
+                let key = meta.path.get_ident().map(ToString::to_string);
+                match key.as_deref() {
+                    Some("dir") => options.dir_lit = Some(meta.value()?.parse()?),
+                    Some("compress") => options.compress_lit = Some(meta.value()?.parse()?),
+                    Some("hash") => options.hash_lit = Some(meta.value()?.parse()?),
+                    Some("text") => options.text_pattern_lit = Some(meta.value()?.parse()?),
+                    Some("dev_reload") => options.dev_reload_lit = Some(meta.value()?.parse()?),
+                    Some("sniff_mime") => options.sniff_mime_lit = Some(meta.value()?.parse()?),
+                    _ => {
+                        return Err(meta.error(
+                            "Expected 'dir', 'compress', 'hash', 'text', 'dev_reload' or 'sniff_mime'",
+                        ));
+                    }
+                }
+                Ok(())
+            })?;
+        }
+
+        Ok(options)
+    }
+}
+
+/// Get the `path` of the `#[asset(path = "...")]` attribute on a variant.
+fn variant_path(attrs: &[syn::Attribute]) -> syn::Result<Option<LitStr>> {
+    let mut path = None;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("asset")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("path") {
+                path = Some(meta.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("Expected 'path'"))
+            }
+        })?;
+    }
+    Ok(path)
+}
+
+/// Build the implementations for `#[derive(Asset)]` on a user-declared enum.
+impl TryFrom<DeriveInput> for AssetEnum {
+    type Error = syn::Error;
+
+    fn try_from(input: DeriveInput) -> Result<Self, Self::Error> {
+        let Data::Enum(data) = &input.data else {
+            return Err(syn::Error::new(
+                input.ident.span(),
+                "`Asset` can only be derived for enums",
+            ));
+        };
+        if !input.generics.params.is_empty() {
+            return Err(syn::Error::new_spanned(
+                &input.generics,
+                "`Asset` cannot be derived for generic enums",
+            ));
+        }
+        if data.variants.is_empty() {
+            return Err(syn::Error::new(
+                input.ident.span(),
+                "`Asset` needs at least one variant",
+            ));
+        }
+
+        let options = EnumOptions::parse(&input)?;
+
+        let cargo_manifest_dir = std::env::var("CARGO_MANIFEST_DIR").map_err(|_| {
+            syn::Error::new(
+                input.ident.span(),
+                "CARGO_MANIFEST_DIR environment variable not set. Are you running inside a Cargo build?",
+            )
+        })?;
+        let mut env_vars = Vec::new();
+        let dir_path = match &options.dir_lit {
+            Some(dir_lit) => {
+                let dir = expand_env_vars(
+                    &dir_lit.value(),
+                    |name| std::env::var(name).ok(),
+                    &mut env_vars,
+                )
+                .map_err(|message| syn::Error::new(dir_lit.span(), message))?;
+                Path::new(&cargo_manifest_dir).join(dir)
+            }
+            None => Path::new(&cargo_manifest_dir).to_path_buf(),
+        };
+
+        let loader = EntryLoader {
+            compression: options
+                .compress_lit
+                .as_ref()
+                .map(Compression::from_lit)
+                .transpose()?,
+            text_regex: options
+                .text_pattern_lit
+                .as_ref()
+                .map(regex_from_lit)
+                .transpose()?,
+            hash_algorithm: options
+                .hash_lit
+                .as_ref()
+                .map(HashAlgorithm::from_lit)
+                .transpose()?
+                .unwrap_or_default(),
+            mime_detector: MimeDetector::new(
+                std::iter::empty(),
+                options.sniff_mime_lit.is_some_and(|lit| lit.value),
+            ),
+        };
+
+        let mut entries = Vec::new();
+        let mut variants_by_path = HashMap::new();
+        for variant in &data.variants {
+            if !matches!(variant.fields, Fields::Unit) {
+                return Err(syn::Error::new_spanned(
+                    &variant.fields,
+                    "Asset variants cannot have fields",
+                ));
+            }
+
+            let Some(path_lit) = variant_path(&variant.attrs)? else {
+                return Err(syn::Error::new(
+                    variant.ident.span(),
+                    format!("Missing `#[asset(path = \"...\")]` on `{}`", variant.ident),
+                ));
+            };
+
+            let rel_path = normalize_rel_path(Path::new(&path_lit.value().replace('\\', "/")));
+            if rel_path == ".." || rel_path.starts_with("../") {
+                return Err(syn::Error::new(
+                    path_lit.span(),
+                    format!("'{}' is outside of the asset directory", path_lit.value()),
+                ));
+            }
+            let full_path = dir_path.join(&rel_path);
+            if !full_path.is_file() {
+                return Err(syn::Error::new(
+                    path_lit.span(),
+                    format!("No file found at '{}'", full_path.display()),
+                ));
+            }
+            if let Some(other) = variants_by_path.insert(rel_path.clone(), &variant.ident) {
+                return Err(syn::Error::new(
+                    path_lit.span(),
+                    format!("'{}' is already the path of `{}`", rel_path, other),
+                ));
+            }
+
+            entries.push(loader.load(
+                variant.ident.clone(),
+                &full_path,
+                rel_path,
+                path_lit.span(),
+            )?);
+        }
+
+        Ok(Self {
+            attrs: Vec::new(),
+            vis: input.vis,
+            enum_name: input.ident,
+            nested: None,
+            aliases: Vec::new(),
+            ignore_files: Vec::new(),
+            scanned_dirs: Vec::new(),
+            env_vars,
+            dev_reload: options.dev_reload_lit.is_some_and(|lit| lit.value),
+            serde: false,
+            clap: false,
+            user_declared: true,
+            entries,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TempDir;
+    use syn::parse_quote;
+
+    #[test]
+    fn test_derive() {
+        let root = TempDir::new("derive");
+        root.write("icons/save.svg", "<svg/>");
+
+        let dir = root.to_string_lossy();
+        let input: DeriveInput = parse_quote! {
+            #[asset(dir = #dir)]
+            enum Icon {
+                #[asset(path = "icons/save.svg")]
+                Save,
+            }
+        };
+        let assets = AssetEnum::try_from(input).unwrap();
+        assert_eq!(assets.entries[0].rel_path, "icons/save.svg");
+        assert!(assets.user_declared);
+
+        let input: DeriveInput = parse_quote! {
+            #[asset(dir = #dir)]
+            enum Icon {
+                #[asset(path = "icons/open.svg")]
+                Open,
+                Save,
+            }
+        };
+        let error = AssetEnum::try_from(input).err().unwrap();
+        assert!(error.to_string().starts_with("No file found"));
+
+        // `icons/save.svg` exists, but must not be found by leaving the directory.
+        let icons_dir = root.join("icons");
+        let icons_dir = icons_dir.to_string_lossy();
+        let input: DeriveInput = parse_quote! {
+            #[asset(dir = #icons_dir)]
+            enum Icon {
+                #[asset(path = "../icons/save.svg")]
+                Save,
+            }
+        };
+        let error = AssetEnum::try_from(input).err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "'../icons/save.svg' is outside of the asset directory"
+        );
+
+        let input: DeriveInput = parse_quote! {
+            enum Icon {
+                Save,
+            }
+        };
+        let error = AssetEnum::try_from(input).err().unwrap();
+        assert!(error.to_string().starts_with("Missing `#[asset(path"));
+
+        let input: DeriveInput = parse_quote! {
+            enum Icon {}
+        };
+        let error = AssetEnum::try_from(input).err().unwrap();
+        assert_eq!(error.to_string(), "`Asset` needs at least one variant");
+    }
+}
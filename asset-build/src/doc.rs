@@ -0,0 +1,175 @@
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+
+use crate::hash::HashAlgorithm;
+
+/// Largest image that is embedded in the documentation as a data URI.
+const MAX_IMAGE_PREVIEW_SIZE: usize = 32 * 1024;
+
+/// Number of lines of text files shown in the documentation.
+const TEXT_PREVIEW_LINES: usize = 5;
+
+/// Longest line of a text file shown in the documentation, in characters.
+const TEXT_PREVIEW_LINE_LENGTH: usize = 100;
+
+/// Metadata and contents of a file, as needed to document its variant.
+pub(crate) struct FileInfo<'a> {
+    pub(crate) rel_path: &'a str,
+    pub(crate) data: &'a [u8],
+    pub(crate) mime_type: &'a str,
+    pub(crate) hash: &'a [u8; 32],
+    pub(crate) hash_algorithm: HashAlgorithm,
+    /// Whether the file matched the `text` pattern.
+    pub(crate) text: bool,
+}
+
+/// Generate the rustdoc of the variant of a file.
+///
+/// Lists the path, size, MIME type and hash of the file, followed by a preview: small images
+/// are shown inline through a data URI and text files with their first lines.
+pub(crate) fn variant_doc(file: &FileInfo) -> String {
+    let hash: String = file
+        .hash
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+    let mut doc = format!(
+        "`{}`\n\n- Size: {}\n- MIME type: `{}`\n- {}: `{}`\n",
+        file.rel_path,
+        format_size(file.data.len()),
+        file.mime_type,
+        file.hash_algorithm.name(),
+        hash
+    );
+
+    if file.mime_type.starts_with("image/") {
+        if file.data.len() <= MAX_IMAGE_PREVIEW_SIZE {
+            doc.push_str(&format!(
+                "\n![{}](data:{};base64,{})\n",
+                file.rel_path,
+                file.mime_type,
+                STANDARD.encode(file.data)
+            ));
+        }
+    } else if (file.text || is_text_mime(file.mime_type))
+        && let Ok(text) = std::str::from_utf8(file.data)
+    {
+        doc.push_str(&text_preview(text));
+    }
+
+    doc
+}
+
+/// Format a size in bytes for humans, e.g. `1.5 KiB (1536 bytes)`.
+fn format_size(size: usize) -> String {
+    const UNITS: [&str; 3] = ["KiB", "MiB", "GiB"];
+
+    if size < 1024 {
+        return format!("{} bytes", size);
+    }
+
+    let mut value = size as f64 / 1024.0;
+    let mut unit = UNITS[0];
+    for next_unit in &UNITS[1..] {
+        if value < 1024.0 {
+            break;
+        }
+        value /= 1024.0;
+        unit = next_unit;
+    }
+    format!("{:.1} {} ({} bytes)", value, unit, size)
+}
+
+/// Check whether files of a MIME type are human-readable text.
+fn is_text_mime(mime_type: &str) -> bool {
+    let essence = mime_type.split(';').next().unwrap_or_default().trim();
+    essence.starts_with("text/")
+        || essence.ends_with("+json")
+        || essence.ends_with("+xml")
+        || matches!(
+            essence,
+            "application/json" | "application/javascript" | "application/xml" | "application/toml"
+        )
+}
+
+/// Show the first lines of a text file in a code block.
+fn text_preview(text: &str) -> String {
+    let mut lines: Vec<String> = text
+        .lines()
+        .take(TEXT_PREVIEW_LINES)
+        .map(
+            |line| match line.char_indices().nth(TEXT_PREVIEW_LINE_LENGTH) {
+                Some((end, _)) => format!("{}…", &line[..end]),
+                None => line.to_string(),
+            },
+        )
+        .collect();
+    if lines.is_empty() {
+        return String::new();
+    }
+    if text.lines().nth(TEXT_PREVIEW_LINES).is_some() {
+        lines.push("…".to_string());
+    }
+
+    // The fence has to be longer than any run of backticks in the text.
+    let longest_run = text
+        .split(|c| c != '`')
+        .map(str::len)
+        .max()
+        .unwrap_or_default();
+    let fence = "`".repeat(longest_run.max(2) + 1);
+
+    format!("\n{}text\n{}\n{}\n", fence, lines.join("\n"), fence)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file<'a>(rel_path: &'a str, data: &'a [u8], mime_type: &'a str) -> FileInfo<'a> {
+        FileInfo {
+            rel_path,
+            data,
+            mime_type,
+            hash: &[0xab; 32],
+            hash_algorithm: HashAlgorithm::Sha256,
+            text: false,
+        }
+    }
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(0), "0 bytes");
+        assert_eq!(format_size(1536), "1.5 KiB (1536 bytes)");
+        assert_eq!(format_size(3 * 1024 * 1024), "3.0 MiB (3145728 bytes)");
+    }
+
+    #[test]
+    fn test_metadata() {
+        let doc = variant_doc(&file("data.bin", &[0; 10], "application/octet-stream"));
+        assert!(doc.starts_with(
+            "`data.bin`\n\n- Size: 10 bytes\n- MIME type: `application/octet-stream`\n"
+        ));
+        assert!(doc.contains(&format!("- SHA-256: `{}`", "ab".repeat(32))));
+    }
+
+    #[test]
+    fn test_image_preview() {
+        let doc = variant_doc(&file("logo.png", b"png", "image/png"));
+        assert!(doc.ends_with("\n![logo.png](data:image/png;base64,cG5n)\n"));
+
+        let large = vec![0; MAX_IMAGE_PREVIEW_SIZE + 1];
+        assert!(!variant_doc(&file("large.png", &large, "image/png")).contains("data:"));
+    }
+
+    #[test]
+    fn test_text_preview() {
+        let doc = variant_doc(&file("a.txt", b"1\n2\n3\n4\n5\n6\n", "text/plain"));
+        assert!(doc.ends_with("\n```text\n1\n2\n3\n4\n5\n…\n```\n"));
+
+        let doc = variant_doc(&file("a.md", b"```rust\n```", "text/markdown"));
+        assert!(doc.ends_with("\n````text\n```rust\n```\n````\n"));
+
+        assert!(!variant_doc(&file("a.bin", b"abc", "application/octet-stream")).contains("```"));
+    }
+}
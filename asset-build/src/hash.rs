@@ -0,0 +1,81 @@
+use sha2::Digest;
+use syn::LitStr;
+
+/// Hash algorithm selected with the `hash` option of the `assets!` macro.
+#[derive(Clone, Copy, Default)]
+pub(crate) enum HashAlgorithm {
+    #[default]
+    Sha256,
+    Blake3,
+}
+
+impl HashAlgorithm {
+    /// Parse the algorithm name.
+    pub(crate) fn from_lit(lit: &LitStr) -> syn::Result<Self> {
+        match lit.value().as_str() {
+            "sha256" => Ok(HashAlgorithm::Sha256),
+            "blake3" => Ok(HashAlgorithm::Blake3),
+            other => Err(syn::Error::new(
+                lit.span(),
+                format!(
+                    "Unknown hash algorithm '{}'. Expected 'sha256' or 'blake3'",
+                    other
+                ),
+            )),
+        }
+    }
+
+    /// Get the display name of the algorithm.
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            HashAlgorithm::Sha256 => "SHA-256",
+            HashAlgorithm::Blake3 => "BLAKE3",
+        }
+    }
+
+    /// Compute the 32-byte digest of the file contents.
+    pub(crate) fn digest(self, data: &[u8]) -> [u8; 32] {
+        match self {
+            HashAlgorithm::Sha256 => sha2::Sha256::digest(data).into(),
+            HashAlgorithm::Blake3 => blake3::hash(data).into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proc_macro2::Span;
+
+    fn hex(digest: [u8; 32]) -> String {
+        digest.iter().map(|byte| format!("{:02x}", byte)).collect()
+    }
+
+    #[test]
+    fn test_digest() {
+        assert_eq!(
+            hex(HashAlgorithm::Sha256.digest(b"abc")),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(
+            hex(HashAlgorithm::Blake3.digest(b"abc")),
+            "6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85"
+        );
+    }
+
+    #[test]
+    fn test_from_lit() {
+        let lit = LitStr::new("blake3", Span::call_site());
+        assert!(matches!(
+            HashAlgorithm::from_lit(&lit),
+            Ok(HashAlgorithm::Blake3)
+        ));
+
+        let lit = LitStr::new("md5", Span::call_site());
+        let error = HashAlgorithm::from_lit(&lit).err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "Unknown hash algorithm 'md5'. Expected 'sha256' or 'blake3'"
+        );
+    }
+}
@@ -0,0 +1,817 @@
+use globset::GlobSet;
+use proc_macro2::Span;
+use quote::{ToTokens, format_ident, quote};
+use regex::Regex;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use syn::{Attribute, Ident, LitStr, Visibility, parse_quote};
+
+use crate::compress::{Compression, compress_data};
+use crate::doc::{FileInfo, variant_doc};
+use crate::hash::HashAlgorithm;
+use crate::layout::{DirTree, Layout};
+use crate::mime::MimeDetector;
+use crate::parse::AssetsInput;
+use crate::utils::{
+    Collision, CollisionPolicy, Filters, build_glob_set, collect_files, expand_env_vars,
+    normalize_rel_path, path_to_variant_name, regex_from_lit, resolve_collisions,
+};
+
+/// The code generated for an asset directory.
+pub struct AssetEnum {
+    /// Outer attributes of the generated enum, in addition to the default derives.
+    pub(crate) attrs: Vec<Attribute>,
+    pub(crate) vis: Visibility,
+    pub(crate) enum_name: Ident,
+    /// Directory tree for the nested layout, `None` for the flat layout.
+    pub(crate) nested: Option<DirTree>,
+    /// Additional paths resolved by `find_by_path`, with the variant they resolve to.
+    pub(crate) aliases: Vec<(String, Ident)>,
+    /// Ignore files that were read, so changes to them trigger a rebuild.
+    pub(crate) ignore_files: Vec<String>,
+    /// Directories that were walked, so adding or removing files can trigger a rebuild.
+    pub(crate) scanned_dirs: Vec<String>,
+    /// Environment variables used in the directory path, so changing them triggers a rebuild.
+    pub(crate) env_vars: Vec<String>,
+    pub(crate) dev_reload: bool,
+    /// Whether to implement `Serialize` and `Deserialize` with the `serde` feature of
+    /// `asset-traits`.
+    pub(crate) serde: bool,
+    /// Whether to implement `clap::ValueEnum` with the `clap` feature of `asset-traits`.
+    pub(crate) clap: bool,
+    /// Whether the enum is declared by the user, so only the trait impls are generated.
+    pub(crate) user_declared: bool,
+    pub(crate) entries: Vec<AssetEntry>,
+}
+
+pub(crate) struct AssetEntry {
+    pub(crate) variant_ident: Ident,
+    full_path: String,
+    pub(crate) rel_path: String,
+    compressed: Option<(Compression, Vec<u8>)>,
+    hash: [u8; 32],
+    mime_type: String,
+    /// Whether the file matched the `text` pattern and was validated as UTF-8.
+    text: bool,
+    /// Documentation of the variant with the file's metadata and a preview.
+    pub(crate) doc: String,
+}
+
+impl TryFrom<AssetsInput> for AssetEnum {
+    type Error = syn::Error;
+
+    fn try_from(value: AssetsInput) -> Result<Self, Self::Error> {
+        let AssetsInput {
+            attrs,
+            vis,
+            enum_name,
+            dir_path_lits,
+            include_pattern_lit,
+            ignore_pattern_lit,
+            compress_lit,
+            hash_lit,
+            mime_overrides,
+            sniff_mime_lit,
+            layout_ident,
+            dev_reload_lit,
+            text_pattern_lit,
+            collision_ident,
+            renames,
+            aliases: alias_input,
+            include_globs,
+            ignore_globs,
+            respect_gitignore_lit,
+            serde_lit,
+            clap_lit,
+        } = value;
+
+        let cargo_manifest_dir = std::env::var("CARGO_MANIFEST_DIR").map_err(|_| syn::Error::new(
+            Span::call_site(),
+            "CARGO_MANIFEST_DIR environment variable not set. Are you running inside a Cargo build?",
+        ))?;
+        let mut env_vars = Vec::new();
+        let mut dirs = Vec::new();
+        for dir_path_lit in &dir_path_lits {
+            let dir_path_str = expand_env_vars(
+                &dir_path_lit.value(),
+                |name| std::env::var(name).ok(),
+                &mut env_vars,
+            )
+            .map_err(|message| syn::Error::new(dir_path_lit.span(), message))?;
+            let dir_path = Path::new(&cargo_manifest_dir).join(&dir_path_str);
+            dirs.push((dir_path_lit, dir_path_str, dir_path));
+        }
+        // Errors that do not belong to a single root point at the first one.
+        let dir_path_lit = &dir_path_lits[0];
+
+        let include_regex = include_pattern_lit
+            .as_ref()
+            .map(regex_from_lit)
+            .transpose()?;
+
+        let ignore_regex = ignore_pattern_lit
+            .as_ref()
+            .map(regex_from_lit)
+            .transpose()?;
+
+        let glob_set = |patterns: Vec<LitStr>| -> syn::Result<Option<GlobSet>> {
+            if patterns.is_empty() {
+                return Ok(None);
+            }
+            let values: Vec<_> = patterns.iter().map(LitStr::value).collect();
+            build_glob_set(values.iter().map(String::as_str))
+                .map(Some)
+                .map_err(|e| {
+                    let span = patterns
+                        .iter()
+                        .find(|pattern| e.glob() == Some(pattern.value().as_str()))
+                        .map_or_else(Span::call_site, LitStr::span);
+                    syn::Error::new(span, format!("Invalid glob pattern: {}", e))
+                })
+        };
+
+        let filters = Filters {
+            include_regex,
+            ignore_regex,
+            include_glob: glob_set(include_globs)?,
+            ignore_glob: glob_set(ignore_globs)?,
+            respect_gitignore: respect_gitignore_lit.is_some_and(|lit| lit.value),
+        };
+
+        let text_regex = text_pattern_lit.as_ref().map(regex_from_lit).transpose()?;
+
+        let compression = compress_lit
+            .as_ref()
+            .map(Compression::from_lit)
+            .transpose()?;
+
+        let hash_algorithm = hash_lit
+            .as_ref()
+            .map(HashAlgorithm::from_lit)
+            .transpose()?
+            .unwrap_or_default();
+
+        let layout = layout_ident
+            .as_ref()
+            .map(Layout::from_ident)
+            .transpose()?
+            .unwrap_or_default();
+
+        let collision_policy = collision_ident
+            .as_ref()
+            .map(CollisionPolicy::from_ident)
+            .transpose()?
+            .unwrap_or_default();
+
+        let mime_detector = MimeDetector::new(
+            mime_overrides
+                .iter()
+                .map(|(ext, mime)| (ext.value(), mime.value())),
+            sniff_mime_lit.is_some_and(|lit| lit.value),
+        );
+
+        // Later roots override files with the same relative path in earlier ones.
+        let mut files = BTreeMap::new();
+        let mut scanned_dirs = Vec::new();
+        let mut ignore_files = Vec::new();
+        for (dir_path_lit, dir_path_str, dir_path) in &dirs {
+            let collected = collect_files(dir_path, &filters).map_err(|e| {
+                syn::Error::new(
+                    dir_path_lit.span(),
+                    format!("Failed to read directory '{}': {}", dir_path_str, e),
+                )
+            })?;
+
+            for path in collected.files {
+                let rel_path = normalize_rel_path(path.strip_prefix(dir_path).unwrap());
+                files.insert(rel_path, (path, *dir_path_lit));
+            }
+            scanned_dirs.extend(collected.dirs);
+            ignore_files.extend(collected.ignore_files);
+        }
+
+        if files.is_empty() {
+            let dir_names: Vec<_> = dirs.iter().map(|(_, name, _)| name.as_str()).collect();
+            let noun = if dirs.len() == 1 {
+                "directory"
+            } else {
+                "directories"
+            };
+            return Err(syn::Error::new(
+                dir_path_lit.span(),
+                format!(
+                    "No matching files found in {} '{}'",
+                    noun,
+                    dir_names.join("', '")
+                ),
+            ));
+        }
+
+        let loader = EntryLoader {
+            compression,
+            text_regex,
+            hash_algorithm,
+            mime_detector,
+        };
+        let mut entries: Vec<AssetEntry> = files
+            .into_iter()
+            .map(|(rel_path, (path, dir_path_lit))| {
+                let variant_ident = format_ident!("{}", path_to_variant_name(&rel_path));
+                loader.load(variant_ident, &path, rel_path, dir_path_lit.span())
+            })
+            .collect::<syn::Result<_>>()?;
+
+        let collision_error = |collision: Collision| {
+            syn::Error::new(
+                dir_path_lit.span(),
+                format!(
+                    "'{}' and '{}' both map to the identifier `{}`. Rename one of them or use `collision: suffix`",
+                    collision.first, collision.second, collision.name
+                ),
+            )
+        };
+
+        // Explicitly renamed files keep their names, generated names give way to them.
+        let mut renamed: BTreeMap<String, String> = BTreeMap::new();
+        let mut rename_idents: HashMap<String, Ident> = HashMap::new();
+        for (path_lit, ident) in renames {
+            let path = normalize_rel_path(Path::new(&path_lit.value().replace('\\', "/")));
+            if !entries.iter().any(|entry| entry.rel_path == path) {
+                return Err(syn::Error::new(
+                    path_lit.span(),
+                    format!("No asset found at '{}' to rename", path),
+                ));
+            }
+            if let Some(other) = renamed.insert(ident.to_string(), path.clone()) {
+                return Err(syn::Error::new(
+                    ident.span(),
+                    format!("`{}` is already the name of '{}'", ident, other),
+                ));
+            }
+            rename_idents.insert(path, ident);
+        }
+
+        let (renamed_entries, generated_entries): (Vec<_>, Vec<_>) = entries
+            .iter_mut()
+            .partition(|entry| rename_idents.contains_key(&entry.rel_path));
+        for entry in renamed_entries {
+            entry.variant_ident = rename_idents[&entry.rel_path].clone();
+        }
+
+        let variant_names: Vec<_> = generated_entries
+            .iter()
+            .map(|entry| (entry.rel_path.clone(), entry.variant_ident.to_string()))
+            .collect();
+        let variant_names = resolve_collisions(&variant_names, &renamed, collision_policy)
+            .map_err(collision_error)?;
+        for (entry, name) in generated_entries.into_iter().zip(variant_names) {
+            entry.variant_ident = format_ident!("{}", name);
+        }
+
+        let mut aliases: Vec<(String, Ident)> = Vec::new();
+        for (path_lit, ident) in alias_input {
+            let path = normalize_rel_path(Path::new(&path_lit.value().replace('\\', "/")));
+            if entries.iter().any(|entry| entry.rel_path == path)
+                || aliases.iter().any(|(alias, _)| *alias == path)
+            {
+                return Err(syn::Error::new(
+                    path_lit.span(),
+                    format!("'{}' is already the path of an asset", path),
+                ));
+            }
+            let Some(entry) = entries.iter().find(|entry| entry.variant_ident == ident) else {
+                return Err(syn::Error::new(
+                    ident.span(),
+                    format!("No asset named `{}`", ident),
+                ));
+            };
+            aliases.push((path, entry.variant_ident.clone()));
+        }
+
+        let nested = match layout {
+            Layout::Flat => None,
+            Layout::Nested => Some(
+                DirTree::new(&entries, &rename_idents, collision_policy)
+                    .map_err(collision_error)?,
+            ),
+        };
+
+        let to_strings = |paths: Vec<std::path::PathBuf>| {
+            paths
+                .iter()
+                .map(|path| path.to_string_lossy().into_owned())
+                .collect()
+        };
+
+        // Without an explicit visibility the enum stays `pub`, as it always was.
+        let vis = match vis {
+            Visibility::Inherited => parse_quote!(pub),
+            vis => vis,
+        };
+
+        Ok(Self {
+            attrs,
+            vis,
+            enum_name,
+            nested,
+            aliases,
+            ignore_files: to_strings(ignore_files),
+            scanned_dirs: to_strings(scanned_dirs),
+            env_vars,
+            dev_reload: dev_reload_lit.is_some_and(|lit| lit.value),
+            serde: serde_lit.is_some_and(|lit| lit.value),
+            clap: clap_lit.is_some_and(|lit| lit.value),
+            user_declared: false,
+            entries,
+        })
+    }
+}
+
+impl AssetEnum {
+    /// Get the directories that were walked to find the assets.
+    pub fn scanned_dirs(&self) -> &[String] {
+        &self.scanned_dirs
+    }
+
+    /// Get the environment variables used in the directory path.
+    pub(crate) fn env_vars(&self) -> &[String] {
+        &self.env_vars
+    }
+
+    /// Get the embedded files and the ignore files that were read.
+    pub(crate) fn tracked_files(&self) -> impl Iterator<Item = &String> {
+        self.entries
+            .iter()
+            .map(|entry| &entry.full_path)
+            .chain(&self.ignore_files)
+    }
+}
+
+/// Settings for turning files into [`AssetEntry`]s.
+pub(crate) struct EntryLoader {
+    pub(crate) compression: Option<Compression>,
+    pub(crate) text_regex: Option<Regex>,
+    pub(crate) hash_algorithm: HashAlgorithm,
+    pub(crate) mime_detector: MimeDetector,
+}
+
+impl EntryLoader {
+    /// Read, compress, validate and hash the file at `path`, reporting errors at `span`.
+    pub(crate) fn load(
+        &self,
+        variant_ident: Ident,
+        path: &Path,
+        rel_path: String,
+        span: Span,
+    ) -> syn::Result<AssetEntry> {
+        let full_path = path.to_string_lossy().into_owned();
+
+        let data = std::fs::read(path)
+            .map_err(|e| syn::Error::new(span, format!("Failed to read '{}': {}", rel_path, e)))?;
+
+        let compressed = match self.compression {
+            Some(compression) => compress_data(&data, compression).map_err(|e| {
+                syn::Error::new(span, format!("Failed to compress '{}': {}", rel_path, e))
+            })?,
+            None => None,
+        };
+
+        let text = self
+            .text_regex
+            .as_ref()
+            .is_some_and(|regex| regex.is_match(&rel_path));
+        if text && let Err(e) = std::str::from_utf8(&data) {
+            return Err(syn::Error::new(
+                span,
+                format!("Text asset '{}' is not valid UTF-8: {}", rel_path, e),
+            ));
+        }
+
+        let hash = self.hash_algorithm.digest(&data);
+        let mime_type = self.mime_detector.detect(path, &data);
+        let doc = variant_doc(&FileInfo {
+            rel_path: &rel_path,
+            data: &data,
+            mime_type: &mime_type,
+            hash: &hash,
+            hash_algorithm: self.hash_algorithm,
+            text,
+        });
+
+        Ok(AssetEntry {
+            variant_ident,
+            full_path,
+            rel_path,
+            compressed,
+            hash,
+            mime_type,
+            text,
+            doc,
+        })
+    }
+}
+
+impl AssetEntry {
+    /// Generate one `#[doc]` attribute per line of the documentation, like `///` comments.
+    pub(crate) fn doc_attrs(&self) -> proc_macro2::TokenStream {
+        let lines = self.doc.lines().map(|line| format!(" {}", line));
+        quote!(#(#[doc = #lines])*)
+    }
+
+    /// Expression evaluating to the path and the (decompressed) bytes of this asset.
+    fn path_and_bytes(&self) -> proc_macro2::TokenStream {
+        let Self {
+            variant_ident,
+            full_path,
+            rel_path,
+            compressed,
+            text,
+            ..
+        } = self;
+
+        match compressed {
+            None if *text => quote! {
+                (#rel_path, Self::#variant_ident.__asset_embedded_text().unwrap().as_bytes())
+            },
+            None => quote! {{
+                const BYTES: &'static [u8] = include_bytes!(#full_path);
+                (#rel_path, BYTES)
+            }},
+            Some(_) => quote! {{
+                // Keeps the file tracked by Cargo without embedding the uncompressed bytes.
+                const _: &'static [u8] = include_bytes!(#full_path);
+                static BYTES: std::sync::OnceLock<Vec<u8>> = std::sync::OnceLock::new();
+                let bytes = BYTES.get_or_init(|| {
+                    let (compression, compressed) = Self::#variant_ident.__asset_compressed().unwrap();
+                    compression.decompress(compressed)
+                });
+                (#rel_path, bytes.as_slice())
+            }},
+        }
+    }
+}
+
+/// Generate a static perfect hash map from relative path or alias to variant, named `PATHS`.
+fn path_map(
+    enum_name: &Ident,
+    entries: &[AssetEntry],
+    aliases: &[(String, Ident)],
+) -> proc_macro2::TokenStream {
+    let paths: Vec<_> = entries
+        .iter()
+        .map(|entry| (entry.rel_path.as_str(), &entry.variant_ident))
+        .chain(aliases.iter().map(|(alias, ident)| (alias.as_str(), ident)))
+        .collect();
+    let keys: Vec<_> = paths.iter().map(|(path, _)| *path).collect();
+    let state = phf_generator::generate_hash(&keys);
+
+    let key = state.key;
+    let disps = state.disps.iter().map(|(d1, d2)| quote!((#d1, #d2)));
+    let map_entries = state.map.iter().map(|&index| {
+        let (path, variant_ident) = paths[index];
+        quote!((#path, #enum_name::#variant_ident))
+    });
+
+    quote! {
+        static PATHS: asset_traits::phf::Map<&'static str, #enum_name> = asset_traits::phf::Map {
+            key: #key,
+            disps: &[#(#disps),*],
+            entries: &[#(#map_entries),*],
+        };
+    }
+}
+
+impl AssetEnum {
+    /// Generate the enum with the given name and its trait implementations.
+    fn enum_tokens(&self, enum_name: &Ident, vis: &Visibility) -> proc_macro2::TokenStream {
+        let entries = &self.entries;
+        let variant_idents: Vec<_> = entries.iter().map(|entry| &entry.variant_ident).collect();
+        let path_and_bytes = entries.iter().map(AssetEntry::path_and_bytes);
+        let hashes = entries.iter().map(|entry| {
+            let bytes = entry.hash.iter();
+            quote!(&[#(#bytes),*])
+        });
+        let mime_types = entries.iter().map(|entry| &entry.mime_type);
+
+        let mut compressed_idents = Vec::new();
+        let mut compressions = Vec::new();
+        let mut compressed_data = Vec::new();
+        for entry in entries {
+            if let Some((compression, data)) = &entry.compressed {
+                compressed_idents.push(&entry.variant_ident);
+                compressions.push(compression);
+                compressed_data.push(proc_macro2::Literal::byte_string(data));
+            }
+        }
+
+        // The helpers of the trait impls are prefixed, so they can't clash with the methods of a
+        // user-declared enum.
+        let compression_impl = (!compressed_idents.is_empty()).then(|| {
+            quote! {
+                impl #enum_name {
+                    fn __asset_compressed(&self) -> Option<(asset_traits::Compression, &'static [u8])> {
+                        #[allow(unreachable_patterns)]
+                        match self {
+                            #(#enum_name::#compressed_idents => Some((#compressions, #compressed_data)),)*
+                            _ => None,
+                        }
+                    }
+                }
+            }
+        });
+
+        let bytes_impl = if self.dev_reload {
+            let full_paths = entries.iter().map(|entry| &entry.full_path);
+            quote! {
+                if cfg!(debug_assertions) {
+                    let full_path = match self {
+                        #(#enum_name::#variant_idents => #full_paths),*
+                    };
+                    return asset_traits::dev::read_live(full_path, || self.__asset_path_and_bytes().1);
+                }
+                self.__asset_path_and_bytes().1
+            }
+        } else {
+            quote!(self.__asset_path_and_bytes().1)
+        };
+
+        let path_map = path_map(enum_name, entries, &self.aliases);
+
+        // Text assets that are stored compressed are validated at compile time, but their text
+        // is only available after decompression.
+        let text_entries: Vec<_> = entries
+            .iter()
+            .filter(|entry| entry.text && entry.compressed.is_none())
+            .collect();
+        let has_text = entries.iter().any(|entry| entry.text);
+
+        let text_impl = has_text.then(|| {
+            let text_idents = text_entries.iter().map(|entry| &entry.variant_ident);
+            let text_paths = text_entries.iter().map(|entry| &entry.full_path);
+            quote! {
+                impl #enum_name {
+                    fn __asset_embedded_text(&self) -> Option<&'static str> {
+                        #[allow(unreachable_patterns)]
+                        match self {
+                            #(#enum_name::#text_idents => {
+                                const TEXT: &'static str = include_str!(#text_paths);
+                                Some(TEXT)
+                            })*
+                            _ => None,
+                        }
+                    }
+                }
+            }
+        });
+
+        let text_method = has_text.then(|| {
+            let dev_reload = self.dev_reload.then(|| {
+                quote! {
+                    if cfg!(debug_assertions) {
+                        return std::str::from_utf8(asset_traits::Asset::bytes(self)).ok();
+                    }
+                }
+            });
+            quote! {
+                fn text(&self) -> Option<&'static str> {
+                    #dev_reload
+                    self.__asset_embedded_text()
+                        .or_else(|| std::str::from_utf8(asset_traits::Asset::bytes(self)).ok())
+                }
+            }
+        });
+
+        let compression_methods = compression_impl.is_some().then(|| {
+            quote! {
+                fn compression(&self) -> Option<asset_traits::Compression> {
+                    self.__asset_compressed().map(|(compression, _)| compression)
+                }
+
+                fn compressed_bytes(&self) -> Option<&'static [u8]> {
+                    self.__asset_compressed().map(|(_, bytes)| bytes)
+                }
+            }
+        });
+
+        let ignore_files = &self.ignore_files;
+        let env_vars = &self.env_vars;
+
+        // Opting in per enum keeps the `serde` and `clap` features additive: enabling them
+        // elsewhere in the dependency graph doesn't add impls that could conflict with the
+        // user's own.
+        let serde_impl = self
+            .serde
+            .then(|| quote!(asset_traits::__impl_serde!(#enum_name);));
+        let clap_impl = self
+            .clap
+            .then(|| quote!(asset_traits::__impl_clap!(#enum_name);));
+
+        // A user-declared enum keeps its own derives, inherent methods and trait impls.
+        let enum_declaration = (!self.user_declared).then(|| {
+            let attrs = &self.attrs;
+            let docs = entries.iter().map(AssetEntry::doc_attrs);
+            quote! {
+                #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+                #(#attrs)*
+                #vis enum #enum_name {
+                    #(
+                        #docs
+                        #variant_idents
+                    ),*
+                }
+
+                impl ::std::str::FromStr for #enum_name {
+                    type Err = asset_traits::UnknownAssetError;
+
+                    fn from_str(path: &str) -> Result<Self, Self::Err> {
+                        <Self as asset_traits::AssetCollection>::find_by_path(path)
+                            .ok_or_else(|| asset_traits::UnknownAssetError::new(path))
+                    }
+                }
+
+                impl ::std::convert::TryFrom<&str> for #enum_name {
+                    type Error = asset_traits::UnknownAssetError;
+
+                    fn try_from(path: &str) -> Result<Self, Self::Error> {
+                        path.parse()
+                    }
+                }
+
+                impl ::std::fmt::Display for #enum_name {
+                    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                        f.write_str(asset_traits::Asset::path(self))
+                    }
+                }
+
+                #serde_impl
+                #clap_impl
+            }
+        });
+        let all_method = (!self.user_declared).then(|| {
+            quote! {
+                /// Get all assets of this type.
+                pub fn all() -> &'static [#enum_name] {
+                    <Self as asset_traits::AssetCollection>::all()
+                }
+            }
+        });
+
+        // The collection type is scoped to an anonymous constant, only `COLLECTION` names it.
+        let collection_name = self.enum_name.to_string();
+        let collection_ident = format_ident!("__{}Collection", enum_name);
+
+        quote! {
+            #(const _: &[u8] = include_bytes!(#ignore_files);)*
+            #(const _: Option<&str> = option_env!(#env_vars);)*
+
+            #enum_declaration
+
+            impl #enum_name {
+                fn __asset_path_and_bytes(&self) -> (&'static str, &'static [u8]) {
+                    match self {
+                        #(#enum_name::#variant_idents => #path_and_bytes),*
+                    }
+                }
+
+                #all_method
+            }
+
+            #compression_impl
+
+            #text_impl
+
+            impl asset_traits::Asset for #enum_name {
+                fn path(&self) -> &'static str {
+                    self.__asset_path_and_bytes().0
+                }
+
+                fn bytes(&self) -> &'static [u8] {
+                    #bytes_impl
+                }
+
+                fn hash(&self) -> &'static [u8; 32] {
+                    match self {
+                        #(#enum_name::#variant_idents => #hashes),*
+                    }
+                }
+
+                fn mime_type(&self) -> &'static str {
+                    match self {
+                        #(#enum_name::#variant_idents => #mime_types),*
+                    }
+                }
+
+                #compression_methods
+
+                #text_method
+            }
+
+            impl asset_traits::AssetCollection for #enum_name {
+                fn all() -> &'static [Self] {
+                    static ALL_ASSETS: &[#enum_name] = &[#(#enum_name::#variant_idents),*];
+                    ALL_ASSETS
+                }
+
+                fn find_by_path(path: &str) -> Option<Self> {
+                    #path_map
+                    PATHS.get(&*asset_traits::normalize_path(path)).copied()
+                }
+            }
+
+            const _: () = {
+                struct #collection_ident;
+
+                impl asset_traits::DynAssetCollection for #collection_ident {
+                    fn name(&self) -> &'static str {
+                        #collection_name
+                    }
+
+                    fn entries(&self) -> &'static [&'static dyn asset_traits::Asset] {
+                        const ENTRIES: &[&dyn asset_traits::Asset] = &[#(&#enum_name::#variant_idents),*];
+                        ENTRIES
+                    }
+
+                    fn find(&self, path: &str) -> Option<&'static dyn asset_traits::Asset> {
+                        let asset = <#enum_name as asset_traits::AssetCollection>::find_by_path(path)?;
+                        Some(match asset {
+                            #(#enum_name::#variant_idents => &#enum_name::#variant_idents),*
+                        })
+                    }
+                }
+
+                impl #enum_name {
+                    /// All assets of this type as a
+                    /// [`DynAssetCollection`](asset_traits::DynAssetCollection).
+                    pub const COLLECTION: &'static dyn asset_traits::DynAssetCollection =
+                        &#collection_ident;
+                }
+            };
+        }
+    }
+}
+
+impl ToTokens for AssetEnum {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        match &self.nested {
+            None => tokens.extend(self.enum_tokens(&self.enum_name, &self.vis)),
+            Some(tree) => {
+                let enum_name = &self.enum_name;
+                let vis = &self.vis;
+                // The visibility applies to the module, the attributes to the `File` enum.
+                let file_enum = self.enum_tokens(&format_ident!("File"), &parse_quote!(pub));
+                let tree = tree.to_tokens(&self.entries);
+                tokens.extend(quote! {
+                    #[allow(non_snake_case)]
+                    #vis mod #enum_name {
+                        #file_enum
+                        #tree
+                    }
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TempDir;
+
+    #[test]
+    fn test_load_text() {
+        let dir = TempDir::new("ir-load-text");
+        dir.write("notes.txt", "hello");
+        dir.write("broken.txt", [0x68, 0x69, 0xff]);
+
+        let loader = EntryLoader {
+            compression: None,
+            text_regex: Some(Regex::new(r"\.txt$").unwrap()),
+            hash_algorithm: HashAlgorithm::default(),
+            mime_detector: MimeDetector::default(),
+        };
+
+        let entry = loader
+            .load(
+                format_ident!("NotesTxt"),
+                &dir.join("notes.txt"),
+                "notes.txt".to_string(),
+                Span::call_site(),
+            )
+            .unwrap();
+        assert!(entry.text);
+
+        let error = loader
+            .load(
+                format_ident!("BrokenTxt"),
+                &dir.join("broken.txt"),
+                "broken.txt".to_string(),
+                Span::call_site(),
+            )
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.to_string(),
+            "Text asset 'broken.txt' is not valid UTF-8: invalid utf-8 sequence of 1 bytes from index 2"
+        );
+    }
+}
@@ -0,0 +1,312 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Component, Path};
+use syn::Ident;
+
+use crate::ir::AssetEntry;
+use crate::utils::{
+    Collision, CollisionPolicy, path_to_module_name, path_to_variant_name, resolve_collisions,
+};
+
+/// Shape of the code generated by the `assets!` macro.
+#[derive(Clone, Copy, Default)]
+pub(crate) enum Layout {
+    /// A single enum with one variant per file.
+    #[default]
+    Flat,
+    /// A module per directory, with constants for the files and a `DIR` listing its children.
+    Nested,
+}
+
+impl Layout {
+    pub(crate) fn from_ident(ident: &Ident) -> syn::Result<Self> {
+        match ident.to_string().as_str() {
+            "flat" => Ok(Layout::Flat),
+            "nested" => Ok(Layout::Nested),
+            other => Err(syn::Error::new(
+                ident.span(),
+                format!("Unknown layout '{}'. Expected 'flat' or 'nested'", other),
+            )),
+        }
+    }
+}
+
+/// Directory hierarchy of the assets, used to generate the nested layout.
+#[derive(Default)]
+pub(crate) struct DirTree {
+    name: String,
+    path: String,
+    /// Constant identifiers of the files directly in this directory and their entry indices.
+    files: Vec<(Ident, usize)>,
+    /// Module identifiers of the subdirectories and their trees.
+    dirs: Vec<(Ident, DirTree)>,
+}
+
+/// Directory hierarchy before identifiers are assigned.
+#[derive(Default)]
+struct DirNode {
+    path: String,
+    files: Vec<usize>,
+    dirs: BTreeMap<String, DirNode>,
+}
+
+impl DirTree {
+    /// Build the tree of `entries`. Files in `renames` keep their explicit names as constants.
+    pub(crate) fn new(
+        entries: &[AssetEntry],
+        renames: &HashMap<String, Ident>,
+        policy: CollisionPolicy,
+    ) -> Result<Self, Collision> {
+        let mut root = DirNode::default();
+
+        for (index, entry) in entries.iter().enumerate() {
+            let rel_path = Path::new(&entry.rel_path);
+            let mut node = &mut root;
+
+            for component in rel_path.parent().into_iter().flat_map(Path::components) {
+                let Component::Normal(name) = component else {
+                    continue;
+                };
+                let name = name.to_string_lossy().into_owned();
+                let path = if node.path.is_empty() {
+                    name.clone()
+                } else {
+                    format!("{}/{}", node.path, name)
+                };
+
+                node = node.dirs.entry(name).or_insert_with(|| DirNode {
+                    path,
+                    ..Default::default()
+                });
+            }
+
+            node.files.push(index);
+        }
+
+        Self::from_node(String::new(), root, entries, renames, policy)
+    }
+
+    fn from_node(
+        name: String,
+        node: DirNode,
+        entries: &[AssetEntry],
+        renames: &HashMap<String, Ident>,
+        policy: CollisionPolicy,
+    ) -> Result<Self, Collision> {
+        // Like in the flat enum, generated names give way to explicit ones.
+        let (renamed_files, generated_files): (Vec<usize>, Vec<usize>) = node
+            .files
+            .iter()
+            .partition(|&&index| renames.contains_key(&entries[index].rel_path));
+        let reserved: BTreeMap<_, _> = renamed_files
+            .iter()
+            .map(|&index| {
+                let rel_path = &entries[index].rel_path;
+                (renames[rel_path].to_string(), rel_path.clone())
+            })
+            .collect();
+        let file_names: Vec<_> = generated_files
+            .iter()
+            .map(|&index| {
+                let rel_path = &entries[index].rel_path;
+                let file_name = Path::new(rel_path).file_name().unwrap_or_default();
+                (rel_path.clone(), path_to_variant_name(file_name))
+            })
+            .collect();
+        let mut files: Vec<_> = resolve_collisions(&file_names, &reserved, policy)?
+            .into_iter()
+            .zip(generated_files)
+            .map(|(name, index)| (format_ident!("{}", name), index))
+            .chain(
+                renamed_files
+                    .into_iter()
+                    .map(|index| (renames[&entries[index].rel_path].clone(), index)),
+            )
+            .collect();
+        files.sort_by_key(|&(_, index)| index);
+
+        let dir_names: Vec<_> = node
+            .dirs
+            .iter()
+            .map(|(name, dir)| (dir.path.clone(), path_to_module_name(name)))
+            .collect();
+        let dirs = resolve_collisions(&dir_names, &BTreeMap::new(), policy)?
+            .into_iter()
+            .zip(node.dirs)
+            .map(|(module_name, (name, dir))| {
+                let tree = Self::from_node(name, dir, entries, renames, policy)?;
+                Ok((format_ident!("{}", module_name), tree))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            name,
+            path: node.path,
+            files,
+            dirs,
+        })
+    }
+
+    /// Generate the items of this directory's module: file constants, `DIR` and submodules.
+    ///
+    /// The generated code expects the `File` enum to be in scope.
+    pub(crate) fn to_tokens(&self, entries: &[AssetEntry]) -> TokenStream {
+        let Self {
+            name,
+            path,
+            files,
+            dirs,
+        } = self;
+
+        let file_idents = files.iter().map(|(ident, _)| ident);
+        let variant_idents: Vec<_> = files
+            .iter()
+            .map(|&(_, index)| &entries[index].variant_ident)
+            .collect();
+        let docs = files.iter().map(|&(_, index)| entries[index].doc_attrs());
+
+        let module_idents: Vec<_> = dirs.iter().map(|(ident, _)| ident).collect();
+        let modules = dirs.iter().map(|(_, dir)| dir.to_tokens(entries));
+
+        quote! {
+            /// This directory and its children.
+            pub const DIR: asset_traits::Directory<File> = asset_traits::Directory::new(
+                #name,
+                #path,
+                &[#(File::#variant_idents),*],
+                &[#(#module_idents::DIR),*],
+            );
+
+            #(
+                #docs
+                #[allow(non_upper_case_globals)]
+                pub const #file_idents: File = File::#variant_idents;
+            )*
+
+            #(
+                pub mod #module_idents {
+                    #[allow(unused_imports)]
+                    use super::File;
+
+                    #modules
+                }
+            )*
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::HashAlgorithm;
+    use crate::ir::EntryLoader;
+    use crate::mime::MimeDetector;
+    use crate::test_utils::TempDir;
+    use proc_macro2::Span;
+
+    fn load_entries(dir: &TempDir, rel_paths: &[&str]) -> Vec<AssetEntry> {
+        let loader = EntryLoader {
+            compression: None,
+            text_regex: None,
+            hash_algorithm: HashAlgorithm::default(),
+            mime_detector: MimeDetector::default(),
+        };
+        rel_paths
+            .iter()
+            .map(|rel_path| {
+                dir.write(rel_path, "");
+                let variant_ident = format_ident!("{}", path_to_variant_name(rel_path));
+                loader
+                    .load(
+                        variant_ident,
+                        &dir.join(rel_path),
+                        rel_path.to_string(),
+                        Span::call_site(),
+                    )
+                    .unwrap()
+            })
+            .collect()
+    }
+
+    fn file_names(tree: &DirTree) -> Vec<String> {
+        tree.files
+            .iter()
+            .map(|(ident, _)| ident.to_string())
+            .collect()
+    }
+
+    fn dir_names(tree: &DirTree) -> Vec<String> {
+        tree.dirs
+            .iter()
+            .map(|(ident, _)| ident.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_dir_tree() {
+        let dir = TempDir::new("layout-tree");
+        let entries = load_entries(
+            &dir,
+            &[
+                "readme.txt",
+                "ui/logo.svg",
+                "ui/icons/save.svg",
+                "User-Data/a.json",
+            ],
+        );
+        let tree = DirTree::new(&entries, &HashMap::new(), CollisionPolicy::Error).unwrap();
+
+        assert_eq!(tree.path, "");
+        assert_eq!(file_names(&tree), ["ReadmeTxt"]);
+        assert_eq!(dir_names(&tree), ["user_data", "ui"]);
+
+        let (_, ui) = &tree.dirs[1];
+        assert_eq!((ui.name.as_str(), ui.path.as_str()), ("ui", "ui"));
+        assert_eq!(file_names(ui), ["LogoSvg"]);
+        assert_eq!(dir_names(ui), ["icons"]);
+
+        let (_, icons) = &ui.dirs[0];
+        assert_eq!(icons.path, "ui/icons");
+        assert_eq!(file_names(icons), ["SaveSvg"]);
+        assert_eq!(icons.files[0].1, 2);
+    }
+
+    #[test]
+    fn test_dir_tree_invalid_module_names() {
+        let dir = TempDir::new("layout-module-names");
+        let entries = load_entries(&dir, &["_/a.txt", "2021/b.txt", "type/c.txt"]);
+        let tree = DirTree::new(&entries, &HashMap::new(), CollisionPolicy::Error).unwrap();
+
+        assert_eq!(dir_names(&tree), ["_2021", "_dir", "r#type"]);
+
+        // The generated modules have to parse as items.
+        let tokens = tree.to_tokens(&entries);
+        syn::parse2::<syn::File>(tokens).unwrap();
+    }
+
+    #[test]
+    fn test_dir_tree_renames() {
+        let dir = TempDir::new("layout-renames");
+        let entries = load_entries(&dir, &["ui/logo.png", "ui/logo_png"]);
+        let renames = HashMap::from([("ui/logo_png".to_string(), format_ident!("LogoPng"))]);
+        let tree = DirTree::new(&entries, &renames, CollisionPolicy::Suffix).unwrap();
+
+        let (_, ui) = &tree.dirs[0];
+        assert_eq!(file_names(ui), ["LogoPng2", "LogoPng"]);
+    }
+
+    #[test]
+    fn test_dir_tree_collision() {
+        let dir = TempDir::new("layout-collision");
+        let entries = load_entries(&dir, &["user-data/a.txt", "user_data/b.txt"]);
+
+        let Err(collision) = DirTree::new(&entries, &HashMap::new(), CollisionPolicy::Error) else {
+            panic!("expected a collision");
+        };
+        assert_eq!(collision.name, "user_data");
+
+        let tree = DirTree::new(&entries, &HashMap::new(), CollisionPolicy::Suffix).unwrap();
+        assert_eq!(dir_names(&tree), ["user_data", "user_data2"]);
+    }
+}
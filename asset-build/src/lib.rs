@@ -0,0 +1,116 @@
+//! Build script helpers for crates embedding assets.
+//!
+//! [`AssetBuilder`] generates the code of the `assets!` macro from a build script, for
+//! directories and options that are only known while the build script runs, or to get the
+//! generated code as a file. The macro itself is implemented with this crate.
+//!
+//! The `assets!` macro recompiles when an embedded file changes, but Cargo does not know that
+//! the generated code also depends on the list of files in the asset directory. Calling
+//! [`track`] from `build.rs` makes adding, removing or renaming an asset rerun the build script,
+//! which recompiles the crate and expands the macro again:
+//!
+//! ```no_run
+//! // build.rs
+//! asset_build::track("assets/ui").unwrap();
+//! ```
+//!
+//! Once a build script emits `rerun-if-changed`, Cargo only reruns it for the listed paths, so
+//! `build.rs` itself has to be tracked explicitly if other parts of the script depend on it.
+
+mod builder;
+mod compress;
+mod derive;
+mod doc;
+mod hash;
+mod ir;
+mod layout;
+mod mime;
+mod parse;
+#[cfg(test)]
+mod test_utils;
+mod utils;
+
+pub use builder::{AssetBuilder, Error};
+pub use utils::path_to_variant_name;
+
+#[doc(hidden)]
+pub use ir::AssetEnum;
+#[doc(hidden)]
+pub use parse::AssetsInput;
+
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// Tell Cargo to rerun the build script when the contents of `dir` change.
+///
+/// Emits `cargo:rerun-if-changed` for `dir` and every directory below it. Relative paths are
+/// resolved against `CARGO_MANIFEST_DIR`, like the directory passed to `assets!`.
+pub fn track(dir: impl AsRef<Path>) -> io::Result<()> {
+    let dir = match std::env::var_os("CARGO_MANIFEST_DIR") {
+        Some(manifest_dir) => Path::new(&manifest_dir).join(dir),
+        None => dir.as_ref().to_path_buf(),
+    };
+    track_to(&dir, &mut io::stdout().lock())
+}
+
+/// Write the `rerun-if-changed` instructions for `dir` to `out`.
+fn track_to(dir: &Path, out: &mut impl Write) -> io::Result<()> {
+    let mut dirs = Vec::new();
+    collect_dirs(dir, &mut dirs)?;
+
+    for dir in dirs {
+        writeln!(out, "cargo:rerun-if-changed={}", dir.display())?;
+    }
+    Ok(())
+}
+
+/// Collect `dir` and all directories below it.
+fn collect_dirs(dir: &Path, dirs: &mut Vec<PathBuf>) -> io::Result<()> {
+    if !dir.is_dir() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("Directory not found: {}", dir.display()),
+        ));
+    }
+
+    dirs.push(dir.to_path_buf());
+
+    let mut subdirs = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            subdirs.push(path);
+        }
+    }
+    subdirs.sort();
+
+    for subdir in subdirs {
+        collect_dirs(&subdir, dirs)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TempDir;
+
+    #[test]
+    fn test_track() {
+        let root = TempDir::new("track");
+        fs::create_dir_all(root.join("ui/icons")).unwrap();
+        fs::create_dir_all(root.join("audio")).unwrap();
+        root.write("ui/logo.png", "");
+
+        let mut out = Vec::new();
+        track_to(&root, &mut out).unwrap();
+        let expected: String = ["", "/audio", "/ui", "/ui/icons"]
+            .iter()
+            .map(|dir| format!("cargo:rerun-if-changed={}{}\n", root.display(), dir))
+            .collect();
+        assert_eq!(String::from_utf8(out).unwrap(), expected);
+
+        assert!(track_to(&root.join("missing"), &mut Vec::new()).is_err());
+    }
+}
@@ -0,0 +1,108 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+/// MIME type used when neither the extension nor the content identify the file.
+const DEFAULT_MIME_TYPE: &str = "application/octet-stream";
+
+/// Rules for detecting the MIME type of an asset at expansion time.
+#[derive(Default)]
+pub(crate) struct MimeDetector {
+    /// MIME types by lowercase file extension, taking precedence over the built-in table.
+    overrides: HashMap<String, String>,
+    /// Whether to sniff magic bytes when the extension is unknown.
+    sniff: bool,
+}
+
+impl MimeDetector {
+    pub(crate) fn new(overrides: impl IntoIterator<Item = (String, String)>, sniff: bool) -> Self {
+        let overrides = overrides
+            .into_iter()
+            .map(|(ext, mime)| (ext.trim_start_matches('.').to_lowercase(), mime))
+            .collect();
+        Self { overrides, sniff }
+    }
+
+    /// Detect the MIME type from the overrides, the file extension and, if enabled, the content.
+    pub(crate) fn detect(&self, path: &Path, data: &[u8]) -> String {
+        let ext = path
+            .extension()
+            .map(|ext| ext.to_string_lossy().to_lowercase());
+
+        if let Some(mime) = ext.as_ref().and_then(|ext| self.overrides.get(ext)) {
+            return mime.clone();
+        }
+
+        if let Some(mime) = ext
+            .as_ref()
+            .and_then(|ext| mime_guess::from_ext(ext).first_raw())
+        {
+            return mime.to_string();
+        }
+
+        if self.sniff
+            && let Some(kind) = infer::get(data)
+        {
+            return kind.mime_type().to_string();
+        }
+
+        DEFAULT_MIME_TYPE.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PNG_MAGIC: &[u8] = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR";
+
+    #[test]
+    fn test_detect_by_extension() {
+        let detector = MimeDetector::default();
+        assert_eq!(detector.detect(Path::new("logo.png"), b""), "image/png");
+        assert_eq!(
+            detector.detect(Path::new("ui/App.JSON"), b""),
+            "application/json"
+        );
+    }
+
+    #[test]
+    fn test_overrides_take_precedence() {
+        let detector = MimeDetector::new(
+            [
+                (
+                    ".json".to_string(),
+                    "application/vnd.custom+json".to_string(),
+                ),
+                ("shader".to_string(), "text/x-glsl".to_string()),
+            ],
+            false,
+        );
+        assert_eq!(
+            detector.detect(Path::new("config.json"), b""),
+            "application/vnd.custom+json"
+        );
+        assert_eq!(
+            detector.detect(Path::new("blur.shader"), b""),
+            "text/x-glsl"
+        );
+    }
+
+    #[test]
+    fn test_sniffing_unknown_extension() {
+        let detector = MimeDetector::new([], true);
+        assert_eq!(detector.detect(Path::new("image"), PNG_MAGIC), "image/png");
+        assert_eq!(
+            detector.detect(Path::new("data.unknownext"), b"\0\0"),
+            DEFAULT_MIME_TYPE
+        );
+    }
+
+    #[test]
+    fn test_no_sniffing_by_default() {
+        let detector = MimeDetector::default();
+        assert_eq!(
+            detector.detect(Path::new("image"), PNG_MAGIC),
+            DEFAULT_MIME_TYPE
+        );
+    }
+}
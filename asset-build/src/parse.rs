@@ -0,0 +1,222 @@
+use proc_macro2::Span;
+use syn::{
+    Attribute, Ident, LitBool, LitStr, Token, Visibility, braced, bracketed, parse::Parse,
+    parse::ParseStream, punctuated::Punctuated,
+};
+
+/// Input parameters for the `assets!` macro.
+pub struct AssetsInput {
+    /// Outer attributes forwarded to the generated enum.
+    pub(crate) attrs: Vec<Attribute>,
+    pub(crate) vis: Visibility,
+    pub(crate) enum_name: Ident,
+    /// The asset roots, later roots overriding files of earlier ones.
+    pub(crate) dir_path_lits: Vec<LitStr>,
+    pub(crate) include_pattern_lit: Option<LitStr>,
+    pub(crate) ignore_pattern_lit: Option<LitStr>,
+    pub(crate) compress_lit: Option<LitStr>,
+    pub(crate) hash_lit: Option<LitStr>,
+    pub(crate) mime_overrides: Vec<(LitStr, LitStr)>,
+    pub(crate) sniff_mime_lit: Option<LitBool>,
+    pub(crate) layout_ident: Option<Ident>,
+    pub(crate) dev_reload_lit: Option<LitBool>,
+    pub(crate) text_pattern_lit: Option<LitStr>,
+    pub(crate) collision_ident: Option<Ident>,
+    pub(crate) renames: Vec<(LitStr, Ident)>,
+    pub(crate) aliases: Vec<(LitStr, Ident)>,
+    pub(crate) include_globs: Vec<LitStr>,
+    pub(crate) ignore_globs: Vec<LitStr>,
+    pub(crate) respect_gitignore_lit: Option<LitBool>,
+    pub(crate) serde_lit: Option<LitBool>,
+    pub(crate) clap_lit: Option<LitBool>,
+}
+
+impl Parse for AssetsInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let vis = input.parse()?;
+        let enum_name = input.parse()?;
+        input.parse::<Token![,]>()?;
+
+        // The directory is either given positionally or with `dirs` as the first option.
+        let dir_path_lit: Option<LitStr> = if input.peek(LitStr) {
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        let mut dirs_lits = None;
+
+        let mut include_pattern_lit = None;
+        let mut ignore_pattern_lit = None;
+        let mut compress_lit = None;
+        let mut hash_lit = None;
+        let mut mime_overrides = Vec::new();
+        let mut sniff_mime_lit = None;
+        let mut layout_ident = None;
+        let mut dev_reload_lit = None;
+        let mut text_pattern_lit = None;
+        let mut collision_ident = None;
+        let mut renames = Vec::new();
+        let mut aliases = Vec::new();
+        let mut include_globs = Vec::new();
+        let mut ignore_globs = Vec::new();
+        let mut respect_gitignore_lit = None;
+        let mut serde_lit = None;
+        let mut clap_lit = None;
+
+        // Parse optional parameters
+        let mut needs_comma = dir_path_lit.is_some();
+        while (!needs_comma && !input.is_empty()) || input.peek(Token![,]) {
+            if needs_comma {
+                input.parse::<Token![,]>()?;
+            }
+            needs_comma = true;
+            let keyword: Ident = input.parse()?;
+            input.parse::<Token![:]>()?;
+
+            match keyword.to_string().as_str() {
+                "dirs" => {
+                    let dirs: Vec<LitStr> = parse_list(input)?;
+                    if dir_path_lit.is_some() {
+                        return Err(syn::Error::new(
+                            keyword.span(),
+                            "The directory is already given. Use either a directory path or `dirs`",
+                        ));
+                    }
+                    if dirs.is_empty() {
+                        return Err(syn::Error::new(
+                            keyword.span(),
+                            "`dirs` needs at least one directory",
+                        ));
+                    }
+                    dirs_lits = Some(dirs);
+                }
+                "include" => {
+                    include_pattern_lit = Some(input.parse()?);
+                }
+                "ignore" => {
+                    ignore_pattern_lit = Some(input.parse()?);
+                }
+                "compress" => {
+                    compress_lit = Some(input.parse()?);
+                }
+                "hash" => {
+                    hash_lit = Some(input.parse()?);
+                }
+                "mime_overrides" => {
+                    mime_overrides = parse_map(input)?;
+                }
+                "sniff_mime" => {
+                    sniff_mime_lit = Some(input.parse()?);
+                }
+                "layout" => {
+                    layout_ident = Some(input.parse()?);
+                }
+                "dev_reload" => {
+                    dev_reload_lit = Some(input.parse()?);
+                }
+                "text" => {
+                    text_pattern_lit = Some(input.parse()?);
+                }
+                "collision" => {
+                    collision_ident = Some(input.parse()?);
+                }
+                "rename" => {
+                    renames = parse_map(input)?;
+                }
+                "alias" => {
+                    aliases = parse_map(input)?;
+                }
+                "include_glob" => {
+                    include_globs = parse_list(input)?;
+                }
+                "ignore_glob" => {
+                    ignore_globs = parse_list(input)?;
+                }
+                "respect_gitignore" => {
+                    respect_gitignore_lit = Some(input.parse()?);
+                }
+                "serde" => {
+                    serde_lit = Some(input.parse()?);
+                }
+                "clap" => {
+                    clap_lit = Some(input.parse()?);
+                }
+                _ => {
+                    return Err(syn::Error::new(
+                        keyword.span(),
+                        "Expected 'dirs', 'include', 'ignore', 'include_glob', 'ignore_glob', 'respect_gitignore', 'compress', 'hash', 'mime_overrides', 'sniff_mime', 'layout', 'dev_reload', 'text', 'collision', 'rename', 'alias', 'serde' or 'clap'",
+                    ));
+                }
+            }
+        }
+
+        let dir_path_lits = match (dir_path_lit, dirs_lits) {
+            (Some(dir_path_lit), _) => vec![dir_path_lit],
+            (None, Some(dirs)) => dirs,
+            (None, None) => {
+                return Err(syn::Error::new(
+                    Span::call_site(),
+                    "Expected a directory path or `dirs: [\"...\", ...]`",
+                ));
+            }
+        };
+
+        Ok(AssetsInput {
+            attrs,
+            vis,
+            enum_name,
+            dir_path_lits,
+            include_pattern_lit,
+            ignore_pattern_lit,
+            compress_lit,
+            hash_lit,
+            mime_overrides,
+            sniff_mime_lit,
+            layout_ident,
+            dev_reload_lit,
+            text_pattern_lit,
+            collision_ident,
+            renames,
+            aliases,
+            include_globs,
+            ignore_globs,
+            respect_gitignore_lit,
+            serde_lit,
+            clap_lit,
+        })
+    }
+}
+
+/// Parse a single string literal or a `["...", ...]` list of them.
+fn parse_list(input: ParseStream) -> syn::Result<Vec<LitStr>> {
+    if input.peek(LitStr) {
+        return Ok(vec![input.parse()?]);
+    }
+
+    let content;
+    bracketed!(content in input);
+    let items = Punctuated::<LitStr, Token![,]>::parse_terminated(&content)?;
+    Ok(items.into_iter().collect())
+}
+
+/// Parse a `{ key => value, ... }` map with an optional trailing comma.
+fn parse_map<K: Parse, V: Parse>(input: ParseStream) -> syn::Result<Vec<(K, V)>> {
+    let content;
+    braced!(content in input);
+
+    let mut entries = Vec::new();
+    while !content.is_empty() {
+        let key = content.parse()?;
+        content.parse::<Token![=>]>()?;
+        let value = content.parse()?;
+        entries.push((key, value));
+
+        if content.is_empty() {
+            break;
+        }
+        content.parse::<Token![,]>()?;
+    }
+
+    Ok(entries)
+}
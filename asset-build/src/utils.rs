@@ -0,0 +1,772 @@
+use convert_case::{Boundary, Case, Converter};
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use ignore::Match;
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use regex::Regex;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use syn::{Ident, LitStr};
+
+/// Filters deciding which files are embedded
+///
+/// Ignore filters take precedence over include filters: a file or directory matching `ignore`
+/// or `ignore_glob` is skipped. Otherwise a file is included if no include filter is given or
+/// if it matches `include` or any of the `include_glob` patterns. Regexes match the full path,
+/// globs match the path relative to the asset root.
+#[derive(Default)]
+pub(crate) struct Filters {
+    pub(crate) include_regex: Option<Regex>,
+    pub(crate) ignore_regex: Option<Regex>,
+    pub(crate) include_glob: Option<GlobSet>,
+    pub(crate) ignore_glob: Option<GlobSet>,
+    pub(crate) respect_gitignore: bool,
+}
+
+impl Filters {
+    fn is_ignored(&self, path: &str, rel_path: &Path) -> bool {
+        self.ignore_regex
+            .as_ref()
+            .is_some_and(|regex| regex.is_match(path))
+            || self
+                .ignore_glob
+                .as_ref()
+                .is_some_and(|glob| glob.is_match(rel_path))
+    }
+
+    fn is_included(&self, path: &str, rel_path: &Path) -> bool {
+        if self.include_regex.is_none() && self.include_glob.is_none() {
+            return true;
+        }
+
+        self.include_regex
+            .as_ref()
+            .is_some_and(|regex| regex.is_match(path))
+            || self
+                .include_glob
+                .as_ref()
+                .is_some_and(|glob| glob.is_match(rel_path))
+    }
+}
+
+/// Compile the regex pattern of a literal, reporting errors at the literal
+pub(crate) fn regex_from_lit(lit: &LitStr) -> syn::Result<Regex> {
+    Regex::new(&lit.value())
+        .map_err(|e| syn::Error::new(lit.span(), format!("Invalid regex pattern: {}", e)))
+}
+
+/// Build a set of glob patterns where `*` does not match path separators but `**` does
+pub(crate) fn build_glob_set<'a>(
+    patterns: impl IntoIterator<Item = &'a str>,
+) -> Result<GlobSet, globset::Error> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        builder.add(GlobBuilder::new(pattern).literal_separator(true).build()?);
+    }
+    builder.build()
+}
+
+/// Name of the file with gitignore-style patterns that is always honored in asset directories
+pub(crate) const ASSET_IGNORE_FILE: &str = ".assetignore";
+
+/// A gitignore-style matcher and whether it applies to the whole path or only its last component
+struct IgnoreMatcher {
+    gitignore: Gitignore,
+    /// Matchers from directories above the asset root also have to check the directories
+    /// between their root and the asset root, which are not visited by the walk.
+    check_parents: bool,
+}
+
+impl IgnoreMatcher {
+    fn matched(&self, path: &Path, is_dir: bool) -> Match<()> {
+        let matched = if self.check_parents {
+            self.gitignore.matched_path_or_any_parents(path, is_dir)
+        } else {
+            self.gitignore.matched(path, is_dir)
+        };
+        matched.map(|_| ())
+    }
+}
+
+/// Name of the gitignore files honored with `respect_gitignore`
+const GIT_IGNORE_FILE: &str = ".gitignore";
+
+/// Load the gitignore-style file at `path`, adding it to `matchers` and `ignore_files`
+///
+/// Its patterns are relative to `root`, which is the directory of the file except for
+/// `.git/info/exclude`, whose patterns are relative to the repository.
+fn load_ignore_file(
+    path: PathBuf,
+    root: &Path,
+    check_parents: bool,
+    matchers: &mut Vec<IgnoreMatcher>,
+    ignore_files: &mut Vec<PathBuf>,
+) -> std::io::Result<()> {
+    if !path.is_file() {
+        return Ok(());
+    }
+
+    let invalid = |error: ignore::Error| {
+        std::io::Error::other(format!("Invalid ignore file {}: {}", path.display(), error))
+    };
+    let mut builder = GitignoreBuilder::new(root);
+    if let Some(error) = builder.add(&path) {
+        return Err(invalid(error));
+    }
+    let gitignore = builder.build().map_err(invalid)?;
+
+    matchers.push(IgnoreMatcher {
+        gitignore,
+        check_parents,
+    });
+    ignore_files.push(path);
+    Ok(())
+}
+
+/// Result of walking an asset directory with [`collect_files`]
+#[derive(Default)]
+pub(crate) struct CollectedFiles {
+    /// The files to embed, sorted by their normalized relative path.
+    pub(crate) files: Vec<PathBuf>,
+    /// The directories that were walked, starting with the root.
+    pub(crate) dirs: Vec<PathBuf>,
+    /// The ignore files that were read.
+    pub(crate) ignore_files: Vec<PathBuf>,
+}
+
+/// Helper function to collect files recursively while applying filters
+///
+/// `.assetignore` files are always honored, `.gitignore` files (including those of the
+/// enclosing git repository and `.git/info/exclude`) if `filters.respect_gitignore` is set.
+/// The ignore files that are honored are never collected themselves.
+///
+/// The files are sorted by their normalized relative path, independently of the order in
+/// which the file system lists them.
+pub(crate) fn collect_files(root: &Path, filters: &Filters) -> std::io::Result<CollectedFiles> {
+    if !root.exists() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!("Directory not found: {}", root.display()),
+        ));
+    }
+
+    let mut collected = CollectedFiles::default();
+    let mut matchers = Vec::new();
+    if filters.respect_gitignore {
+        // Ignore files above the asset root only apply inside the same git repository.
+        let mut candidates = Vec::new();
+        for ancestor in root.ancestors().skip(1) {
+            candidates.push((ancestor.join(GIT_IGNORE_FILE), ancestor));
+            if ancestor.join(".git").is_dir() {
+                candidates.push((ancestor.join(".git/info/exclude"), ancestor));
+                for (candidate, candidate_root) in candidates.drain(..).rev() {
+                    load_ignore_file(
+                        candidate,
+                        candidate_root,
+                        true,
+                        &mut matchers,
+                        &mut collected.ignore_files,
+                    )?;
+                }
+                break;
+            }
+        }
+    }
+
+    collect_files_in(root, root, &mut collected, filters, &mut matchers)?;
+    collected
+        .files
+        .sort_by_cached_key(|path| normalize_rel_path(path.strip_prefix(root).unwrap_or(path)));
+    Ok(collected)
+}
+
+fn collect_files_in(
+    root: &Path,
+    dir: &Path,
+    collected: &mut CollectedFiles,
+    filters: &Filters,
+    matchers: &mut Vec<IgnoreMatcher>,
+) -> std::io::Result<()> {
+    collected.dirs.push(dir.to_path_buf());
+
+    let outer_matchers = matchers.len();
+    if filters.respect_gitignore {
+        load_ignore_file(
+            dir.join(GIT_IGNORE_FILE),
+            dir,
+            false,
+            matchers,
+            &mut collected.ignore_files,
+        )?;
+    }
+    load_ignore_file(
+        dir.join(ASSET_IGNORE_FILE),
+        dir,
+        false,
+        matchers,
+        &mut collected.ignore_files,
+    )?;
+
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        let is_dir = path.is_dir();
+
+        let path_str = path.to_string_lossy();
+        let rel_path = path.strip_prefix(root).unwrap_or(&path);
+
+        // Ignore files are configuration, not assets.
+        let file_name = path.file_name();
+        if filters.is_ignored(&path_str, rel_path)
+            || file_name == Some(ASSET_IGNORE_FILE.as_ref())
+            || (filters.respect_gitignore && file_name == Some(GIT_IGNORE_FILE.as_ref()))
+        {
+            continue;
+        }
+
+        // The innermost ignore file that has an opinion wins, like in git.
+        let ignored = matchers
+            .iter()
+            .rev()
+            .map(|matcher| matcher.matched(&path, is_dir))
+            .find(|matched| !matched.is_none())
+            .is_some_and(|matched| matched.is_ignore());
+        if ignored {
+            continue;
+        }
+
+        if is_dir {
+            collect_files_in(root, &path, collected, filters, matchers)?;
+        } else if filters.is_included(&path_str, rel_path) {
+            collected.files.push(path);
+        }
+    }
+
+    matchers.truncate(outer_matchers);
+    Ok(())
+}
+
+/// Expand `$VAR` and `${VAR}` in a path with the values returned by `lookup`
+///
+/// `$$` stands for a literal `$`. The names of the expanded variables are added to `vars`.
+pub(crate) fn expand_env_vars(
+    path: &str,
+    lookup: impl Fn(&str) -> Option<String>,
+    vars: &mut Vec<String>,
+) -> Result<String, String> {
+    let is_name_char = |c: char| c.is_ascii_alphanumeric() || c == '_';
+    let mut expanded = String::with_capacity(path.len());
+    let mut rest = path;
+
+    while let Some(index) = rest.find('$') {
+        expanded.push_str(&rest[..index]);
+        rest = &rest[index + 1..];
+
+        let name = if let Some(escaped) = rest.strip_prefix('$') {
+            expanded.push('$');
+            rest = escaped;
+            continue;
+        } else if let Some(braced) = rest.strip_prefix('{') {
+            let end = braced
+                .find('}')
+                .ok_or_else(|| format!("Missing `}}` after `${{` in '{}'", path))?;
+            rest = &braced[end + 1..];
+            &braced[..end]
+        } else {
+            let end = rest.find(|c| !is_name_char(c)).unwrap_or(rest.len());
+            let name = &rest[..end];
+            rest = &rest[end..];
+            name
+        };
+
+        if name.is_empty() || !name.chars().all(is_name_char) {
+            return Err(format!(
+                "Expected a variable name after `$` in '{}'. Use `$$` for a literal `$`",
+                path
+            ));
+        }
+
+        let value = lookup(name).ok_or_else(|| {
+            let hint = if name == "OUT_DIR" {
+                ". OUT_DIR is only set for crates with a build script"
+            } else {
+                ""
+            };
+            format!(
+                "Environment variable `{}` used in '{}' is not set{}",
+                name, path, hint
+            )
+        })?;
+        expanded.push_str(&value);
+        vars.push(name.to_string());
+    }
+
+    expanded.push_str(rest);
+    Ok(expanded)
+}
+
+/// Join the components of a relative path with `/`, whatever the platform's separator
+///
+/// `.` components are dropped and `..` removes the preceding component. Like
+/// `asset_traits::normalize_path`, `..` components that would leave the root are kept.
+pub(crate) fn normalize_rel_path(path: &Path) -> String {
+    let mut components: Vec<Cow<str>> = Vec::new();
+    for component in path.components() {
+        match component {
+            Component::Normal(name) => components.push(name.to_string_lossy()),
+            Component::ParentDir if components.last().is_some_and(|last| last != "..") => {
+                components.pop();
+            }
+            Component::ParentDir => components.push(Cow::Borrowed("..")),
+            _ => {}
+        }
+    }
+    components.join("/")
+}
+
+/// Convert file path to a valid enum variant name in UpperCamelCase
+///
+/// This is the name `assets!` gives a file unless it is renamed or collides with another one,
+/// e.g. `UiUserProfilePng` for `ui/user-profile.png`.
+pub fn path_to_variant_name<P: AsRef<Path>>(path: P) -> String {
+    let path_str = path.as_ref().to_string_lossy();
+
+    let conv = Converter::new()
+        .add_boundaries(&[
+            Boundary::from_delim("/"),
+            Boundary::from_delim(r"\"),
+            Boundary::from_delim("."),
+        ])
+        .to_case(Case::Pascal);
+
+    let variant_name = conv.convert(path_str);
+
+    // Try to ensure it's a valid Rust identifier
+    if variant_name.starts_with(|first: char| first.is_numeric()) {
+        format!("Asset{}", variant_name)
+    } else {
+        variant_name
+    }
+}
+
+/// Convert a directory name to a valid module name in snake_case
+pub(crate) fn path_to_module_name<P: AsRef<Path>>(path: P) -> String {
+    let path_str = path.as_ref().to_string_lossy();
+
+    let conv = Converter::new()
+        .add_boundaries(&[Boundary::from_delim(".")])
+        .to_case(Case::Snake);
+
+    let module_name = conv.convert(path_str);
+
+    // Try to ensure it's a valid Rust identifier. Names without letters or digits, like `_`,
+    // convert to an empty string, and `_` alone is not an identifier.
+    if module_name.is_empty() {
+        "_dir".to_string()
+    } else if module_name.starts_with(|first: char| first.is_numeric()) {
+        format!("_{}", module_name)
+    } else if matches!(module_name.as_str(), "self" | "super" | "crate") {
+        format!("{}_", module_name)
+    } else if syn::parse_str::<syn::Ident>(&module_name).is_err() {
+        format!("r#{}", module_name)
+    } else {
+        module_name
+    }
+}
+
+/// How to handle files or directories that map to the same identifier.
+#[derive(Clone, Copy, Default)]
+pub(crate) enum CollisionPolicy {
+    /// Report a compile error naming both files.
+    #[default]
+    Error,
+    /// Append a numeric suffix to all but the first file in path order.
+    Suffix,
+}
+
+impl CollisionPolicy {
+    pub(crate) fn from_ident(ident: &Ident) -> syn::Result<Self> {
+        match ident.to_string().as_str() {
+            "error" => Ok(CollisionPolicy::Error),
+            "suffix" => Ok(CollisionPolicy::Suffix),
+            other => Err(syn::Error::new(
+                ident.span(),
+                format!(
+                    "Unknown collision policy '{}'. Expected 'error' or 'suffix'",
+                    other
+                ),
+            )),
+        }
+    }
+}
+
+/// Two paths that map to the same identifier.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct Collision {
+    pub(crate) first: String,
+    pub(crate) second: String,
+    pub(crate) name: String,
+}
+
+/// Make generated identifiers unique according to the collision policy
+///
+/// `items` are `(path, name)` pairs. `reserved` maps names that are already taken, e.g. by
+/// explicit renames, to the path that owns them. Returns the unique names in the same order.
+pub(crate) fn resolve_collisions(
+    items: &[(String, String)],
+    reserved: &BTreeMap<String, String>,
+    policy: CollisionPolicy,
+) -> Result<Vec<String>, Collision> {
+    let mut groups: BTreeMap<&str, Vec<usize>> = BTreeMap::new();
+    for (index, (_, name)) in items.iter().enumerate() {
+        groups.entry(name).or_default().push(index);
+    }
+
+    let mut used: HashSet<String> = items
+        .iter()
+        .map(|(_, name)| name.clone())
+        .chain(reserved.keys().cloned())
+        .collect();
+    let mut names: Vec<String> = items.iter().map(|(_, name)| name.clone()).collect();
+
+    for (name, mut indices) in groups {
+        let reserved_path = reserved.get(name);
+        if indices.len() < 2 && reserved_path.is_none() {
+            continue;
+        }
+        indices.sort_by(|&a, &b| items[a].0.cmp(&items[b].0));
+
+        // The owner of a reserved name keeps it, otherwise the first path in order does.
+        let (first, renamed) = match reserved_path {
+            Some(path) => (path, &indices[..]),
+            None => (&items[indices[0]].0, &indices[1..]),
+        };
+
+        match policy {
+            CollisionPolicy::Error => {
+                return Err(Collision {
+                    first: first.clone(),
+                    second: items[renamed[0]].0.clone(),
+                    name: name.to_string(),
+                });
+            }
+            CollisionPolicy::Suffix => {
+                let mut suffix = 2;
+                for &index in renamed {
+                    while used.contains(&format!("{}{}", name, suffix)) {
+                        suffix += 1;
+                    }
+                    names[index] = format!("{}{}", name, suffix);
+                    used.insert(names[index].clone());
+                }
+            }
+        }
+    }
+
+    Ok(names)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TempDir;
+
+    #[test]
+    fn test_basic_file_paths() {
+        assert_eq!(path_to_variant_name("image.png"), "ImagePng");
+        assert_eq!(path_to_variant_name("style.css"), "StyleCss");
+    }
+
+    #[test]
+    fn test_nested_paths() {
+        assert_eq!(path_to_variant_name("ui/button.svg"), "UiButtonSvg");
+        assert_eq!(
+            path_to_variant_name("assets/icons/home.png"),
+            "AssetsIconsHomePng"
+        );
+    }
+
+    #[test]
+    fn test_windows_path_separators() {
+        assert_eq!(path_to_variant_name(r"ui\button.svg"), "UiButtonSvg");
+        assert_eq!(
+            path_to_variant_name(r"assets\icons\home.png"),
+            "AssetsIconsHomePng"
+        );
+    }
+
+    #[test]
+    fn test_paths_with_hyphens() {
+        assert_eq!(path_to_variant_name("user-icon.png"), "UserIconPng");
+        assert_eq!(
+            path_to_variant_name("ui/user-profile/avatar_small.jpg"),
+            "UiUserProfileAvatarSmallJpg"
+        );
+    }
+
+    #[test]
+    fn test_paths_with_underscores() {
+        assert_eq!(path_to_variant_name("button_large.png"), "ButtonLargePng");
+    }
+
+    #[test]
+    fn test_paths_starting_with_numbers() {
+        assert_eq!(path_to_variant_name("1icon.png"), "Asset1IconPng");
+        assert_eq!(path_to_variant_name("2021/logo.png"), "Asset2021LogoPng");
+    }
+
+    #[test]
+    fn test_paths_with_multiple_dots() {
+        assert_eq!(path_to_variant_name("config.dev.json"), "ConfigDevJson");
+    }
+
+    #[test]
+    fn test_module_names() {
+        assert_eq!(path_to_module_name("ui"), "ui");
+        assert_eq!(path_to_module_name("user-profile"), "user_profile");
+        assert_eq!(path_to_module_name("UserProfile"), "user_profile");
+        assert_eq!(path_to_module_name("v1.2"), "v_1_2");
+    }
+
+    #[test]
+    fn test_module_names_invalid_identifiers() {
+        assert_eq!(path_to_module_name("2021"), "_2021");
+        assert_eq!(path_to_module_name("type"), "r#type");
+        assert_eq!(path_to_module_name("self"), "self_");
+        assert_eq!(path_to_module_name("_"), "_dir");
+        assert_eq!(path_to_module_name("--"), "_dir");
+    }
+
+    fn items(items: &[(&str, &str)]) -> Vec<(String, String)> {
+        items
+            .iter()
+            .map(|(path, name)| (path.to_string(), name.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_no_collisions() {
+        let items = items(&[("a.png", "APng"), ("b.png", "BPng")]);
+        assert_eq!(
+            resolve_collisions(&items, &BTreeMap::new(), CollisionPolicy::Error).unwrap(),
+            ["APng", "BPng"]
+        );
+    }
+
+    #[test]
+    fn test_collision_error() {
+        let items = items(&[
+            ("config/dev.json", "ConfigDevJson"),
+            ("config.dev.json", "ConfigDevJson"),
+        ]);
+        assert_eq!(
+            resolve_collisions(&items, &BTreeMap::new(), CollisionPolicy::Error),
+            Err(Collision {
+                first: "config.dev.json".to_string(),
+                second: "config/dev.json".to_string(),
+                name: "ConfigDevJson".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_collision_suffix() {
+        let items = items(&[
+            ("a_b.png", "ABPng"),
+            ("a-b.png", "ABPng"),
+            ("a.b.png", "ABPng"),
+            ("x.png", "ABPng2"),
+        ]);
+        assert_eq!(
+            resolve_collisions(&items, &BTreeMap::new(), CollisionPolicy::Suffix).unwrap(),
+            ["ABPng4", "ABPng", "ABPng3", "ABPng2"]
+        );
+    }
+
+    #[test]
+    fn test_collision_with_reserved_name() {
+        let items = items(&[("logo.png", "Logo"), ("other.png", "OtherPng")]);
+        let reserved = BTreeMap::from([("Logo".to_string(), "legacy/old.png".to_string())]);
+        assert_eq!(
+            resolve_collisions(&items, &reserved, CollisionPolicy::Error),
+            Err(Collision {
+                first: "legacy/old.png".to_string(),
+                second: "logo.png".to_string(),
+                name: "Logo".to_string(),
+            })
+        );
+        assert_eq!(
+            resolve_collisions(&items, &reserved, CollisionPolicy::Suffix).unwrap(),
+            ["Logo2", "OtherPng"]
+        );
+    }
+
+    fn filters(include: &[&str], ignore: &[&str]) -> Filters {
+        Filters {
+            include_glob: (!include.is_empty())
+                .then(|| build_glob_set(include.iter().copied()).unwrap()),
+            ignore_glob: (!ignore.is_empty())
+                .then(|| build_glob_set(ignore.iter().copied()).unwrap()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_include_globs() {
+        let filters = filters(&["**/*.png", "icons/*.svg"], &[]);
+        assert!(filters.is_included("", Path::new("logo.png")));
+        assert!(filters.is_included("", Path::new("ui/buttons/ok.png")));
+        assert!(filters.is_included("", Path::new("icons/home.svg")));
+        assert!(!filters.is_included("", Path::new("icons/nested/home.svg")));
+        assert!(!filters.is_included("", Path::new("readme.md")));
+    }
+
+    #[test]
+    fn test_globs_match_relative_path() {
+        let filters = filters(&[], &["temp"]);
+        assert!(!filters.is_ignored("/home/temp/assets/logo.png", Path::new("logo.png")));
+        assert!(filters.is_ignored("/home/assets/temp", Path::new("temp")));
+    }
+
+    #[test]
+    fn test_include_regex_or_glob() {
+        let filters = Filters {
+            include_regex: Some(Regex::new(r"\.json$").unwrap()),
+            ..filters(&["*.png"], &[])
+        };
+        assert!(filters.is_included("/assets/config.json", Path::new("config.json")));
+        assert!(filters.is_included("/assets/logo.png", Path::new("logo.png")));
+        assert!(!filters.is_included("/assets/readme.md", Path::new("readme.md")));
+    }
+
+    #[test]
+    fn test_ignore_files() {
+        let root = TempDir::new("ignore");
+        root.write(".assetignore", "*.swp\n.DS_Store\n");
+        root.write("ui/.gitignore", "build/\n");
+        for file in [
+            "logo.png",
+            "logo.png.swp",
+            ".DS_Store",
+            "ui/button.png",
+            "ui/build/out.png",
+        ] {
+            root.write(file, "");
+        }
+
+        let collect = |respect_gitignore| {
+            let filters = Filters {
+                respect_gitignore,
+                ..Default::default()
+            };
+            collect_files(&root, &filters)
+                .unwrap()
+                .files
+                .iter()
+                .map(|file| normalize_rel_path(file.strip_prefix(&root).unwrap()))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            collect(false),
+            [
+                "logo.png",
+                "ui/.gitignore",
+                "ui/build/out.png",
+                "ui/button.png"
+            ]
+        );
+        assert_eq!(collect(true), ["logo.png", "ui/button.png"]);
+    }
+
+    #[test]
+    fn test_repository_ignore_files() {
+        let repo = TempDir::new("repository");
+        fs::create_dir_all(repo.join(".git/info")).unwrap();
+        repo.write(".git/info/exclude", "*.log\nassets/drafts/\n");
+        repo.write(".gitignore", "*.tmp\n");
+        for file in [
+            "assets/logo.png",
+            "assets/debug.log",
+            "assets/cache.tmp",
+            "assets/drafts/logo.png",
+        ] {
+            repo.write(file, "");
+        }
+
+        let root = repo.join("assets");
+        let filters = Filters {
+            respect_gitignore: true,
+            ..Default::default()
+        };
+        let collected = collect_files(&root, &filters).unwrap();
+        assert_eq!(collected.files, [root.join("logo.png")]);
+        assert_eq!(
+            collected.ignore_files,
+            [repo.join(".git/info/exclude"), repo.join(".gitignore")]
+        );
+    }
+
+    #[test]
+    fn test_collect_files_sorted() {
+        let root = TempDir::new("sorted");
+        for file in ["b.txt", "a/b/c.txt", "a.txt", "a/a.txt", "A.txt", "a-b.txt"] {
+            root.write(file, "");
+        }
+
+        let collected = collect_files(&root, &Filters::default()).unwrap();
+        let files: Vec<_> = collected
+            .files
+            .iter()
+            .map(|file| normalize_rel_path(file.strip_prefix(&root).unwrap()))
+            .collect();
+        assert_eq!(
+            files,
+            ["A.txt", "a-b.txt", "a.txt", "a/a.txt", "a/b/c.txt", "b.txt"]
+        );
+    }
+
+    #[test]
+    fn test_normalize_rel_path() {
+        assert_eq!(normalize_rel_path(Path::new("ui/logo.png")), "ui/logo.png");
+        assert_eq!(
+            normalize_rel_path(Path::new("./ui//logo.png")),
+            "ui/logo.png"
+        );
+        assert_eq!(
+            normalize_rel_path(Path::new("ui/icons/../logo.png")),
+            "ui/logo.png"
+        );
+        assert_eq!(normalize_rel_path(Path::new("../logo.png")), "../logo.png");
+        assert_eq!(
+            normalize_rel_path(Path::new("ui/../../a/../logo.png")),
+            "../logo.png"
+        );
+    }
+
+    #[test]
+    fn test_expand_env_vars() {
+        let lookup = |name: &str| (name == "OUT_DIR").then(|| "/target/out".to_string());
+        let mut vars = Vec::new();
+
+        assert_eq!(
+            expand_env_vars("$OUT_DIR/generated", lookup, &mut vars).unwrap(),
+            "/target/out/generated"
+        );
+        assert_eq!(
+            expand_env_vars("${OUT_DIR}_ui/$$x", lookup, &mut vars).unwrap(),
+            "/target/out_ui/$x"
+        );
+        assert_eq!(
+            expand_env_vars("assets/ui", lookup, &mut vars).unwrap(),
+            "assets/ui"
+        );
+        assert_eq!(vars, ["OUT_DIR", "OUT_DIR"]);
+
+        let error = expand_env_vars("$ASSET_DIR/ui", lookup, &mut vars).unwrap_err();
+        assert!(error.contains("`ASSET_DIR`"));
+        assert!(expand_env_vars("${OUT_DIR/ui", lookup, &mut vars).is_err());
+        assert!(expand_env_vars("$/ui", lookup, &mut vars).is_err());
+    }
+}
@@ -1,26 +1,102 @@
-mod ir;
-mod parse;
-mod utils;
+#![cfg_attr(feature = "nightly", feature(proc_macro_tracked_path))]
 
-use ir::AssetEnum;
-use parse::AssetsInput;
+use asset_build::{AssetEnum, AssetsInput};
 use proc_macro::TokenStream;
 use quote::ToTokens;
-use syn::parse_macro_input;
+use syn::{DeriveInput, parse_macro_input};
 
 /// A macro that generates an enum containing all assets in a directory.
 ///
 /// # Parameters
 ///
+/// * `attributes` - Optional. Outer attributes and doc comments before the name are added to
+///   the generated enum, e.g. `#[derive(PartialOrd, Ord)]`. The enum always derives `Debug`,
+///   `Clone`, `Copy`, `PartialEq`, `Eq` and `Hash`.
+/// * `visibility` - Optional. The visibility of the generated enum, e.g. `pub(crate)`. Defaults
+///   to `pub`; use `pub(self)` for a private enum.
 /// * `enum_name` - Required. The identifier for the generated enum.
 /// * `dir_path` - Required. A string literal specifying the directory path to scan for assets.
+///   Relative paths are resolved against `CARGO_MANIFEST_DIR`. `$VAR` and `${VAR}` are replaced
+///   with the value of the environment variable `VAR` at compile time, e.g. `"$OUT_DIR/generated"`
+///   for files written by a build script; `$$` is a literal `$`. Unset variables are an error.
+/// * `dirs` - Alternative to `dir_path`. A list of directories, e.g.
+///   `dirs: ["assets/common", "assets/desktop"]`, merged into one enum. A file in a later
+///   directory replaces the file with the same relative path in earlier ones. Filters apply to
+///   each directory, relative to that directory.
 /// * `include` - Optional. A regex pattern string literal specifying which files to include.
+///   Matched against the full path of the file.
 /// * `ignore` - Optional. A regex pattern string literal specifying which files to ignore.
+///   Matched against the full path of the file or directory.
+/// * `include_glob` - Optional. A glob pattern or a list of glob patterns matched against the
+///   path relative to `dir_path`, e.g. `["**/*.png", "icons/*.svg"]`. `*` does not match `/`,
+///   `**` does.
+/// * `ignore_glob` - Optional. Like `include_glob`, for files and directories to skip.
+/// * `respect_gitignore` - Optional. When `true`, files ignored by `.gitignore` files in the
+///   asset directory and the enclosing git repository, or by `.git/info/exclude`, are skipped,
+///   and so are the `.gitignore` files themselves.
+/// * `compress` - Optional. Store the assets compressed with `"gzip"`, `"brotli"` or `"zstd"`.
+///   Requires the feature of the same name on both `asset-macros` and `asset-traits`.
+///   Files that do not get smaller are stored uncompressed.
+/// * `hash` - Optional. The algorithm used for [`Asset::hash`](asset_traits::Asset::hash),
+///   `"sha256"` (default) or `"blake3"`.
+/// * `mime_overrides` - Optional. A `{ "ext" => "mime/type", ... }` map that takes precedence
+///   over the built-in extension table for [`Asset::mime_type`](asset_traits::Asset::mime_type).
+/// * `sniff_mime` - Optional. When `true`, files with an unknown extension get their MIME type
+///   from their magic bytes instead of `application/octet-stream`.
+/// * `layout` - Optional. `flat` (default) generates a single enum. `nested` generates a module
+///   named `enum_name` that mirrors the directory structure: the enum of all files is called
+///   `File` and gets the attributes, every directory becomes a snake_case submodule, every file
+///   a constant of its directory's module, and each module has a `DIR` constant of type
+///   [`Directory`](asset_traits::Directory) listing its children. The visibility applies to
+///   the module.
+/// * `dev_reload` - Optional. When `true`, debug builds read the files from disk on every call
+///   to [`Asset::bytes`](asset_traits::Asset::bytes), so edits show up without recompiling.
+///   Release builds always use the embedded bytes. Hashes and MIME types are computed at
+///   compile time and do not follow the edits.
+/// * `text` - Optional. A regex pattern string literal matched against the path relative to
+///   `dir_path`. Matching files must be valid UTF-8, which is checked at compile time, and are
+///   returned by [`Asset::text`](asset_traits::Asset::text) without further validation.
+/// * `collision` - Optional. What to do when several files map to the same identifier, e.g.
+///   `config.dev.json` and `config/dev.json`. `error` (default) reports both files, `suffix`
+///   appends `2`, `3`, ... to all but the first file in path order.
+/// * `rename` - Optional. A `{ "relative/path.png" => VariantName, ... }` map overriding the
+///   variant names generated from the paths, and the constant names in the directory modules of
+///   the nested layout. Generated names give way to explicit ones.
+/// * `alias` - Optional. A `{ "old/path.png" => VariantName, ... }` map of additional paths
+///   that [`find_by_path`](asset_traits::AssetCollection::find_by_path) resolves to a variant.
+/// * `serde` - Optional. When `true`, the enum implements `Serialize` and `Deserialize` as its
+///   asset path. Requires the `serde` feature of `asset-traits`.
+/// * `clap` - Optional. When `true`, the enum implements `clap::ValueEnum` with the asset paths
+///   as possible values. Requires the `clap` feature of `asset-traits`.
+///
+/// # Filters
+///
+/// Ignore filters take precedence over include filters: files and directories matching `ignore`
+/// or `ignore_glob` are skipped. If any include filter is given, a file is embedded when it
+/// matches `include` or at least one `include_glob` pattern; otherwise all files are embedded.
+///
+/// In addition, `.assetignore` files anywhere in the asset directory are always honored. They
+/// use the same syntax as `.gitignore` files and apply to the directory they are in.
+///
+/// # Rebuilds
+///
+/// Changes to embedded files recompile the crate. Adding, removing or renaming files does not
+/// on stable Rust, because the compiler does not track directories for proc macros: call
+/// `asset_build::track` with the asset directory from the crate's build script. With the
+/// `nightly` feature, the macro registers every directory it scans with the compiler instead.
 ///
 /// # Syntax
 ///
 /// ```ignore
-/// assets!(EnumName, "directory/path"[, include: "regex_pattern"][, ignore: "regex_pattern"]);
+/// assets!([#[attribute] ...][visibility] EnumName, "directory/path" | dirs: ["directory/path", ...]
+///         [, include: "regex_pattern"][, ignore: "regex_pattern"]
+///         [, include_glob: ["glob", ...]][, ignore_glob: ["glob", ...]][, respect_gitignore: bool]
+///         [, compress: "algorithm"][, hash: "algorithm"]
+///         [, mime_overrides: { "ext" => "mime/type" }][, sniff_mime: bool]
+///         [, layout: flat | nested][, dev_reload: bool]
+///         [, text: "regex_pattern"][, collision: error | suffix]
+///         [, rename: { "path" => Variant }][, alias: { "path" => Variant }]
+///         [, serde: bool][, clap: bool]);
 /// ```
 ///
 /// # Example
@@ -32,6 +108,19 @@ use syn::parse_macro_input;
 /// ```
 ///
 /// This will generate an enum `UiAssets` with variants for each PNG and JPG file in the "assets/ui" directory.
+///
+/// Each variant is documented with the path, size, MIME type and hash of its file. Images up to
+/// 32 KiB are shown inline, and text files with their first lines.
+///
+/// The variants are declared in the order of the files' paths relative to `dir_path`, compared
+/// byte by byte with `/` as the separator, so [`AssetCollection::all`](asset_traits::AssetCollection::all)
+/// returns the assets in the same order on every machine.
+///
+/// The `COLLECTION` constant of the enum gives access to its assets as a
+/// `&'static dyn` [`DynAssetCollection`](asset_traits::DynAssetCollection).
+///
+/// The enum implements `FromStr` and `TryFrom<&str>` through `find_by_path`, failing with
+/// [`UnknownAssetError`](asset_traits::UnknownAssetError), and `Display` prints the asset path.
 #[proc_macro]
 pub fn assets(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as AssetsInput);
@@ -41,5 +130,52 @@ pub fn assets(input: TokenStream) -> TokenStream {
             return e.to_compile_error().into();
         }
     };
+
+    // Files are tracked through `include_bytes!`, but only the compiler can be told to watch
+    // directories for new files. On stable, `asset_build::track` does the same from build.rs.
+    #[cfg(feature = "nightly")]
+    for dir in ir.scanned_dirs() {
+        proc_macro::tracked::path(dir);
+    }
+
     ir.into_token_stream().into()
 }
+
+/// Implements `Asset` and `AssetCollection` for a user-declared enum.
+///
+/// Every variant names its file with `#[asset(path = "...")]`. The paths are relative to the
+/// directory given with `#[asset(dir = "...")]` on the enum, or to `CARGO_MANIFEST_DIR`.
+/// Missing files and paths leaving that directory are a compile error. The enum keeps its own
+/// visibility, documentation and derives, but has to be `Clone` and `Copy`.
+///
+/// The enum attribute also accepts the `compress`, `hash`, `text`, `dev_reload` and
+/// `sniff_mime` options of [`assets!`].
+///
+/// The enum also gets a `COLLECTION` constant, a `&'static dyn`
+/// [`DynAssetCollection`](asset_traits::DynAssetCollection). Conversion, serde and clap impls
+/// are not generated for derived enums. With the `serde` feature of
+/// `asset-traits`, fields can use `#[serde(with = "asset_traits::serde")]` instead.
+///
+/// # Example
+///
+/// ```ignore
+/// use asset_macros::Asset;
+///
+/// /// Icons shown in the toolbar.
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, Asset)]
+/// #[asset(dir = "assets/ui", hash = "blake3")]
+/// pub(crate) enum Icon {
+///     #[asset(path = "icons/save.svg")]
+///     Save,
+///     #[asset(path = "icons/open.svg")]
+///     Open,
+/// }
+/// ```
+#[proc_macro_derive(Asset, attributes(asset))]
+pub fn derive_asset(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match AssetEnum::try_from(input) {
+        Ok(ir) => ir.into_token_stream().into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
@@ -0,0 +1,108 @@
+use asset_macros::assets;
+use asset_traits::{Asset, AssetCollection, DynAssetCollection};
+
+assets!(
+    Fixtures,
+    "tests/fixtures",
+    alias: { "logo.svg" => UiLogoSvg }
+);
+
+#[test]
+fn test_all() {
+    let paths: Vec<_> = Fixtures::all().iter().map(Asset::path).collect();
+    assert_eq!(
+        paths,
+        [
+            "config.json",
+            "readme.txt",
+            "ui/icons/save.svg",
+            "ui/logo.svg"
+        ]
+    );
+}
+
+#[test]
+fn test_find_by_path() {
+    assert_eq!(
+        Fixtures::find_by_path("ui/logo.svg"),
+        Some(Fixtures::UiLogoSvg)
+    );
+    assert_eq!(
+        Fixtures::find_by_path("config.json"),
+        Some(Fixtures::ConfigJson)
+    );
+    assert_eq!(Fixtures::find_by_path("ui/missing.svg"), None);
+    assert_eq!(Fixtures::find_by_path("ui"), None);
+}
+
+#[test]
+fn test_find_by_alias() {
+    assert_eq!(
+        Fixtures::find_by_path("logo.svg"),
+        Some(Fixtures::UiLogoSvg)
+    );
+    assert_eq!(
+        Fixtures::find_by_path("./logo.svg"),
+        Some(Fixtures::UiLogoSvg)
+    );
+}
+
+#[test]
+fn test_find_by_normalized_path() {
+    assert_eq!(
+        Fixtures::find_by_path("ui\\icons\\save.svg"),
+        Some(Fixtures::UiIconsSaveSvg)
+    );
+    assert_eq!(
+        Fixtures::find_by_path("./ui//icons/../logo.svg"),
+        Some(Fixtures::UiLogoSvg)
+    );
+    assert_eq!(Fixtures::find_by_path("../fixtures/config.json"), None);
+}
+
+#[test]
+fn test_contents() {
+    let config = Fixtures::ConfigJson;
+    assert_eq!(config.bytes(), include_bytes!("fixtures/config.json"));
+    assert_eq!(config.mime_type(), "application/json");
+    assert_eq!(config.text(), Some("{ \"theme\": \"dark\" }\n"));
+}
+
+#[test]
+fn test_collection() {
+    let collection: &dyn DynAssetCollection = Fixtures::COLLECTION;
+    assert_eq!(collection.name(), "Fixtures");
+
+    let paths: Vec<_> = collection
+        .entries()
+        .iter()
+        .map(|asset| asset.path())
+        .collect();
+    assert_eq!(
+        paths,
+        [
+            "config.json",
+            "readme.txt",
+            "ui/icons/save.svg",
+            "ui/logo.svg"
+        ]
+    );
+
+    let logo = collection.find("ui\\logo.svg").unwrap();
+    assert_eq!(logo.path(), "ui/logo.svg");
+    assert_eq!(collection.find("logo.svg").unwrap().path(), "ui/logo.svg");
+    assert!(collection.find("ui/missing.svg").is_none());
+}
+
+#[test]
+fn test_conversions() {
+    assert_eq!("ui\\logo.svg".parse::<Fixtures>(), Ok(Fixtures::UiLogoSvg));
+    assert_eq!(Fixtures::try_from("logo.svg"), Ok(Fixtures::UiLogoSvg));
+
+    let error = Fixtures::try_from("missing").unwrap_err();
+    assert_eq!(error.path(), "missing");
+    assert!("missing".parse::<Fixtures>().is_err());
+
+    assert_eq!(Fixtures::UiLogoSvg.to_string(), "ui/logo.svg");
+    assert_eq!(format!("{}", Fixtures::ConfigJson), "config.json");
+}
@@ -0,0 +1,18 @@
+use asset_macros::assets;
+use clap::ValueEnum;
+
+assets!(Fixtures, "tests/fixtures", clap: true);
+
+#[test]
+fn test_value_enum() {
+    assert_eq!(Fixtures::value_variants().len(), 4);
+    assert_eq!(
+        Fixtures::UiLogoSvg.to_possible_value().unwrap().get_name(),
+        "ui/logo.svg"
+    );
+    assert_eq!(
+        Fixtures::from_str("config.json", false),
+        Ok(Fixtures::ConfigJson)
+    );
+    assert!(Fixtures::from_str("missing.json", false).is_err());
+}
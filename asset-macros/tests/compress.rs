@@ -0,0 +1,18 @@
+use asset_macros::assets;
+use asset_traits::Asset;
+
+assets!(Gzip, "tests/fixtures", compress: "gzip");
+assets!(Brotli, "tests/fixtures", compress: "brotli");
+assets!(Zstd, "tests/fixtures", compress: "zstd");
+
+#[test]
+fn test_compressed_contents() {
+    let expected = include_bytes!("fixtures/config.json");
+    assert_eq!(Gzip::ConfigJson.bytes(), expected);
+    assert_eq!(Brotli::ConfigJson.bytes(), expected);
+    assert_eq!(Zstd::ConfigJson.bytes(), expected);
+    assert_eq!(
+        Zstd::ReadmeTxt.text(),
+        Some("Fixtures for the macro tests.\n")
+    );
+}
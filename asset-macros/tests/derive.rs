@@ -0,0 +1,53 @@
+use asset_macros::Asset;
+use asset_traits::{Asset, AssetCollection, DynAssetCollection};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Asset)]
+#[asset(dir = "tests/fixtures", text = r"\.txt$")]
+enum Docs {
+    #[asset(path = "readme.txt")]
+    Readme,
+    #[asset(path = "ui/logo.svg")]
+    Logo,
+}
+
+// Methods of the enum must not clash with the helpers of the generated impls.
+#[allow(dead_code)]
+impl Docs {
+    fn path_and_bytes(&self) -> u32 {
+        1
+    }
+
+    fn compressed(&self) -> u32 {
+        2
+    }
+
+    fn embedded_text(&self) -> u32 {
+        3
+    }
+
+    fn bytes(&self) -> u32 {
+        4
+    }
+}
+
+#[test]
+fn test_derive() {
+    assert_eq!(Docs::all(), [Docs::Readme, Docs::Logo]);
+    assert_eq!(Docs::find_by_path("ui\\logo.svg"), Some(Docs::Logo));
+    assert_eq!(
+        Asset::bytes(&Docs::Logo),
+        include_bytes!("fixtures/ui/logo.svg")
+    );
+    assert_eq!(Docs::Readme.text(), Some("Fixtures for the macro tests.\n"));
+}
+
+#[test]
+fn test_derive_collection() {
+    let collection: &dyn DynAssetCollection = Docs::COLLECTION;
+    assert_eq!(collection.name(), "Docs");
+    assert_eq!(collection.entries().len(), 2);
+    assert_eq!(
+        collection.find("./readme.txt").unwrap().path(),
+        "readme.txt"
+    );
+}
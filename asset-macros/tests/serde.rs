@@ -0,0 +1,24 @@
+use asset_macros::assets;
+
+assets!(Fixtures, "tests/fixtures", serde: true);
+
+#[test]
+fn test_serialize() {
+    assert_eq!(
+        serde_json::to_string(&Fixtures::UiLogoSvg).unwrap(),
+        r#""ui/logo.svg""#
+    );
+}
+
+#[test]
+fn test_deserialize() {
+    let assets: Vec<Fixtures> =
+        serde_json::from_str(r#"["config.json", "ui\\icons\\save.svg"]"#).unwrap();
+    assert_eq!(assets, [Fixtures::ConfigJson, Fixtures::UiIconsSaveSvg]);
+
+    let error = serde_json::from_str::<Fixtures>(r#""ui/logo.png""#).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "unknown asset path 'ui/logo.png', did you mean 'ui/logo.svg'? at line 1 column 13"
+    );
+}
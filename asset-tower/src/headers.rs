@@ -0,0 +1,193 @@
+//! Parsing of the request headers understood by [`AssetService`](crate::AssetService).
+
+use std::ops::Range;
+
+/// Outcome of resolving a `Range` header against the length of an asset.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum ByteRange {
+    /// Serve the whole asset, either because no valid single range was requested or because
+    /// the range covers everything.
+    Full,
+    /// Serve only the given bytes.
+    Partial(Range<usize>),
+    /// The range lies outside of the asset.
+    Unsatisfiable,
+}
+
+/// Resolve a `Range` header value against an asset of `len` bytes.
+///
+/// Only single `bytes` ranges are supported. Multiple ranges and malformed values are ignored,
+/// which the HTTP specification allows.
+pub(crate) fn parse_range(header: &str, len: usize) -> ByteRange {
+    let Some(spec) = header.trim().strip_prefix("bytes=") else {
+        return ByteRange::Full;
+    };
+    if spec.contains(',') {
+        return ByteRange::Full;
+    }
+    let Some((start, end)) = spec.trim().split_once('-') else {
+        return ByteRange::Full;
+    };
+
+    let range = match (start.trim(), end.trim()) {
+        ("", "") => return ByteRange::Full,
+        ("", suffix) => {
+            let Ok(suffix) = suffix.parse::<usize>() else {
+                return ByteRange::Full;
+            };
+            if suffix == 0 {
+                return ByteRange::Unsatisfiable;
+            }
+            len.saturating_sub(suffix)..len
+        }
+        (start, end) => {
+            let Ok(start) = start.parse::<usize>() else {
+                return ByteRange::Full;
+            };
+            let end = match end {
+                "" => len,
+                end => match end.parse::<usize>() {
+                    Ok(end) if end >= start => end.saturating_add(1).min(len),
+                    _ => return ByteRange::Full,
+                },
+            };
+            if start >= len {
+                return ByteRange::Unsatisfiable;
+            }
+            start..end
+        }
+    };
+
+    if range.is_empty() {
+        ByteRange::Unsatisfiable
+    } else if range == (0..len) {
+        ByteRange::Full
+    } else {
+        ByteRange::Partial(range)
+    }
+}
+
+/// Get the quality with which an `Accept-Encoding` header value accepts `encoding`.
+///
+/// Returns `0.0` when the encoding is not acceptable.
+pub(crate) fn encoding_quality(header: &str, encoding: &str) -> f32 {
+    let mut wildcard = None;
+
+    for item in header.split(',') {
+        let mut parts = item.split(';');
+        let name = parts.next().unwrap_or_default().trim();
+        let quality = parts
+            .filter_map(|param| param.trim().strip_prefix("q="))
+            .find_map(|q| q.trim().parse::<f32>().ok())
+            .unwrap_or(1.0);
+
+        if name.eq_ignore_ascii_case(encoding) {
+            return quality;
+        }
+        if name == "*" {
+            wildcard = Some(quality);
+        }
+    }
+
+    wildcard.unwrap_or(0.0)
+}
+
+/// Check whether an `If-None-Match` header value matches the entity tag of an asset.
+///
+/// Uses the weak comparison required for `If-None-Match`.
+pub(crate) fn etag_matches(header: &str, etag: &str) -> bool {
+    let etag = etag.trim_start_matches("W/");
+    header
+        .split(',')
+        .map(str::trim)
+        .any(|candidate| candidate == "*" || candidate.trim_start_matches("W/") == etag)
+}
+
+/// Decode the percent-encoded path of a request URI.
+///
+/// Returns `None` if the result is not valid UTF-8.
+pub(crate) fn percent_decode(path: &str) -> Option<String> {
+    let bytes = path.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+
+    String::from_utf8(decoded).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ranges() {
+        assert_eq!(parse_range("bytes=0-9", 100), ByteRange::Partial(0..10));
+        assert_eq!(parse_range("bytes=90-", 100), ByteRange::Partial(90..100));
+        assert_eq!(parse_range("bytes=-10", 100), ByteRange::Partial(90..100));
+        assert_eq!(
+            parse_range("bytes=50-500", 100),
+            ByteRange::Partial(50..100)
+        );
+    }
+
+    #[test]
+    fn test_full_ranges() {
+        assert_eq!(parse_range("bytes=0-", 100), ByteRange::Full);
+        assert_eq!(parse_range("bytes=-500", 100), ByteRange::Full);
+        assert_eq!(parse_range("bytes=0-9, 20-29", 100), ByteRange::Full);
+        assert_eq!(parse_range("bytes=9-0", 100), ByteRange::Full);
+        assert_eq!(parse_range("items=0-9", 100), ByteRange::Full);
+        assert_eq!(parse_range("bytes=abc", 100), ByteRange::Full);
+    }
+
+    #[test]
+    fn test_unsatisfiable_ranges() {
+        assert_eq!(parse_range("bytes=100-", 100), ByteRange::Unsatisfiable);
+        assert_eq!(parse_range("bytes=-0", 100), ByteRange::Unsatisfiable);
+        assert_eq!(parse_range("bytes=0-", 0), ByteRange::Unsatisfiable);
+    }
+
+    #[test]
+    fn test_encoding_quality() {
+        assert_eq!(encoding_quality("gzip, br", "br"), 1.0);
+        assert_eq!(encoding_quality("gzip;q=0.5, br;q=0.8", "gzip"), 0.5);
+        assert_eq!(encoding_quality("gzip", "zstd"), 0.0);
+        assert_eq!(encoding_quality("br;q=0, *", "br"), 0.0);
+        assert_eq!(encoding_quality("*;q=0.1", "zstd"), 0.1);
+    }
+
+    #[test]
+    fn test_etag_matches() {
+        assert!(etag_matches("\"abc\"", "\"abc\""));
+        assert!(etag_matches("\"xyz\", W/\"abc\"", "\"abc\""));
+        assert!(etag_matches("*", "\"abc\""));
+        assert!(!etag_matches("\"xyz\"", "\"abc\""));
+    }
+
+    #[test]
+    fn test_percent_decode() {
+        assert_eq!(
+            percent_decode("ui/my%20logo.png").unwrap(),
+            "ui/my logo.png"
+        );
+        assert_eq!(percent_decode("100%").unwrap(), "100%");
+        assert_eq!(percent_decode("%C3%A9").unwrap(), "é");
+        assert!(percent_decode("%FF").is_none());
+    }
+}
@@ -0,0 +1,345 @@
+//! Tower service serving compiled assets over HTTP.
+//!
+//! [`AssetService`] serves every asset of an [`AssetCollection`] under its path, so it can be
+//! mounted in any tower-based server, e.g. with axum's `Router::nest_service`.
+
+mod headers;
+
+use asset_traits::AssetCollection;
+use bytes::Bytes;
+use http::{HeaderMap, HeaderValue, Method, Request, Response, StatusCode, header};
+use http_body_util::Full;
+use std::convert::Infallible;
+use std::future::{Ready, ready};
+use std::marker::PhantomData;
+use std::task::{Context, Poll};
+
+use headers::{ByteRange, encoding_quality, etag_matches, parse_range, percent_decode};
+
+/// Extensions of precompressed sibling files and the encodings they are served with,
+/// in order of preference.
+const PRECOMPRESSED_EXTENSIONS: &[(&str, &str)] = &[("br", "br"), ("zst", "zstd"), ("gz", "gzip")];
+
+/// A [`tower_service::Service`] serving the assets of the collection `C`.
+///
+/// A request for `/ui/logo.png` is answered with the asset whose path is `ui/logo.png`. The
+/// service supports:
+///
+/// * `GET` and `HEAD` requests, with `Content-Type` taken from [`Asset::mime_type`](asset_traits::Asset::mime_type).
+/// * `ETag` and `If-None-Match`, based on [`Asset::hash`](asset_traits::Asset::hash).
+/// * Single `Range` requests, with `If-Range`.
+/// * Precompressed variants negotiated with `Accept-Encoding`: assets stored compressed with the
+///   `compress` option of `assets!` and sibling files such as `app.js.br`, `app.js.zst` or
+///   `app.js.gz` in the same collection.
+pub struct AssetService<C> {
+    _collection: PhantomData<fn() -> C>,
+}
+
+impl<C> AssetService<C> {
+    /// Create a service serving the assets of `C`.
+    pub fn new() -> Self {
+        Self {
+            _collection: PhantomData,
+        }
+    }
+}
+
+impl<C> Default for AssetService<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C> Clone for AssetService<C> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C> Copy for AssetService<C> {}
+
+impl<C> std::fmt::Debug for AssetService<C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AssetService")
+            .field("collection", &std::any::type_name::<C>())
+            .finish()
+    }
+}
+
+impl<C, B> tower_service::Service<Request<B>> for AssetService<C>
+where
+    C: AssetCollection + Copy,
+{
+    type Response = Response<Full<Bytes>>;
+    type Error = Infallible;
+    type Future = Ready<Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: Request<B>) -> Self::Future {
+        ready(Ok(serve::<C>(
+            request.method(),
+            request.uri().path(),
+            request.headers(),
+        )))
+    }
+}
+
+/// A representation of an asset that can be sent to the client.
+struct Representation {
+    bytes: &'static [u8],
+    encoding: Option<&'static str>,
+    etag: String,
+}
+
+/// Format a hash as a strong entity tag, with an optional suffix for encoded representations.
+fn etag(hash: &[u8; 32], suffix: Option<&str>) -> String {
+    let hex: String = hash.iter().map(|byte| format!("{:02x}", byte)).collect();
+    match suffix {
+        Some(suffix) => format!("\"{}-{}\"", hex, suffix),
+        None => format!("\"{}\"", hex),
+    }
+}
+
+/// Get the encoded representations of an asset, in order of preference.
+fn encoded_representations<C>(asset: C) -> Vec<Representation>
+where
+    C: AssetCollection + Copy,
+{
+    let mut representations = Vec::new();
+
+    if let (Some(compression), Some(bytes)) = (asset.compression(), asset.compressed_bytes()) {
+        let encoding = compression.encoding();
+        representations.push(Representation {
+            bytes,
+            encoding: Some(encoding),
+            etag: etag(asset.hash(), Some(encoding)),
+        });
+    }
+
+    for &(extension, encoding) in PRECOMPRESSED_EXTENSIONS {
+        let sibling_path = format!("{}.{}", asset.path(), extension);
+        if let Some(sibling) = C::find_by_path(&sibling_path) {
+            representations.push(Representation {
+                bytes: sibling.bytes(),
+                encoding: Some(encoding),
+                etag: etag(sibling.hash(), None),
+            });
+        }
+    }
+
+    representations
+}
+
+/// Build the response to a request for `path`.
+fn serve<C>(method: &Method, path: &str, headers: &HeaderMap) -> Response<Full<Bytes>>
+where
+    C: AssetCollection + Copy,
+{
+    if method != Method::GET && method != Method::HEAD {
+        return Response::builder()
+            .status(StatusCode::METHOD_NOT_ALLOWED)
+            .header(header::ALLOW, "GET, HEAD")
+            .body(Full::default())
+            .unwrap();
+    }
+
+    let asset =
+        percent_decode(path.trim_start_matches('/')).and_then(|path| C::find_by_path(&path));
+    let Some(asset) = asset else {
+        return Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .body(Full::default())
+            .unwrap();
+    };
+
+    let header_str = |name| {
+        headers
+            .get(name)
+            .and_then(|value: &HeaderValue| value.to_str().ok())
+    };
+    let range_header = header_str(header::RANGE);
+
+    let encoded = encoded_representations(asset);
+    let vary = !encoded.is_empty();
+
+    // Ranges are only served for the identity representation.
+    let mut representation = None;
+    if let (Some(accept_encoding), None) = (header_str(header::ACCEPT_ENCODING), range_header) {
+        let mut best_quality = 0.0;
+        for candidate in encoded {
+            let quality = encoding_quality(accept_encoding, candidate.encoding.unwrap_or_default());
+            if quality > best_quality {
+                best_quality = quality;
+                representation = Some(candidate);
+            }
+        }
+    }
+    let representation = representation.unwrap_or_else(|| Representation {
+        bytes: asset.bytes(),
+        encoding: None,
+        etag: etag(asset.hash(), None),
+    });
+
+    let mut response = Response::builder()
+        .header(header::CONTENT_TYPE, asset.mime_type())
+        .header(header::ETAG, &representation.etag)
+        .header(header::ACCEPT_RANGES, "bytes");
+    if vary {
+        response = response.header(header::VARY, "Accept-Encoding");
+    }
+    if let Some(encoding) = representation.encoding {
+        response = response.header(header::CONTENT_ENCODING, encoding);
+    }
+
+    if header_str(header::IF_NONE_MATCH)
+        .is_some_and(|tags| etag_matches(tags, &representation.etag))
+    {
+        return response
+            .status(StatusCode::NOT_MODIFIED)
+            .body(Full::default())
+            .unwrap();
+    }
+
+    let bytes = representation.bytes;
+    let if_range_matches =
+        header_str(header::IF_RANGE).is_none_or(|tag| tag.trim() == representation.etag);
+    let range = match range_header {
+        Some(range) if if_range_matches => parse_range(range, bytes.len()),
+        _ => ByteRange::Full,
+    };
+
+    let (status, body) = match range {
+        ByteRange::Full => (StatusCode::OK, bytes),
+        ByteRange::Partial(range) => {
+            response = response.header(
+                header::CONTENT_RANGE,
+                format!("bytes {}-{}/{}", range.start, range.end - 1, bytes.len()),
+            );
+            (StatusCode::PARTIAL_CONTENT, &bytes[range])
+        }
+        ByteRange::Unsatisfiable => {
+            return response
+                .status(StatusCode::RANGE_NOT_SATISFIABLE)
+                .header(header::CONTENT_RANGE, format!("bytes */{}", bytes.len()))
+                .body(Full::default())
+                .unwrap();
+        }
+    };
+
+    let content_length = body.len();
+    let body = if method == Method::HEAD {
+        Full::default()
+    } else {
+        Full::new(Bytes::from_static(body))
+    };
+    response
+        .status(status)
+        .header(header::CONTENT_LENGTH, content_length)
+        .body(body)
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use asset_traits::Asset;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum TestAssets {
+        App,
+        AppBrotli,
+    }
+
+    impl Asset for TestAssets {
+        fn path(&self) -> &'static str {
+            match self {
+                TestAssets::App => "app.js",
+                TestAssets::AppBrotli => "app.js.br",
+            }
+        }
+
+        fn bytes(&self) -> &'static [u8] {
+            match self {
+                TestAssets::App => b"console.log('hello');",
+                TestAssets::AppBrotli => b"compressed",
+            }
+        }
+
+        fn hash(&self) -> &'static [u8; 32] {
+            match self {
+                TestAssets::App => &[1; 32],
+                TestAssets::AppBrotli => &[2; 32],
+            }
+        }
+
+        fn mime_type(&self) -> &'static str {
+            "text/javascript"
+        }
+    }
+
+    impl AssetCollection for TestAssets {
+        fn all() -> &'static [Self] {
+            &[TestAssets::App, TestAssets::AppBrotli]
+        }
+    }
+
+    fn get(path: &str, headers: &[(header::HeaderName, &str)]) -> Response<Full<Bytes>> {
+        let mut request = Request::get(path);
+        for (name, value) in headers {
+            request = request.header(name, *value);
+        }
+        let request = request.body(()).unwrap();
+        serve::<TestAssets>(request.method(), request.uri().path(), request.headers())
+    }
+
+    #[test]
+    fn test_serves_asset() {
+        let response = get("/app.js", &[]);
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "text/javascript");
+        assert_eq!(response.headers()[header::CONTENT_LENGTH], "21");
+        assert_eq!(response.headers()[header::VARY], "Accept-Encoding");
+        assert!(!response.headers().contains_key(header::CONTENT_ENCODING));
+    }
+
+    #[test]
+    fn test_missing_asset() {
+        assert_eq!(get("/missing.js", &[]).status(), StatusCode::NOT_FOUND);
+    }
+
+    #[test]
+    fn test_not_modified() {
+        let etag = get("/app.js", &[]).headers()[header::ETAG].clone();
+        let response = get(
+            "/app.js",
+            &[(header::IF_NONE_MATCH, etag.to_str().unwrap())],
+        );
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+    }
+
+    #[test]
+    fn test_range() {
+        let response = get("/app.js", &[(header::RANGE, "bytes=0-6")]);
+        assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+        assert_eq!(response.headers()[header::CONTENT_RANGE], "bytes 0-6/21");
+        assert_eq!(response.headers()[header::CONTENT_LENGTH], "7");
+
+        let response = get("/app.js", &[(header::RANGE, "bytes=100-")]);
+        assert_eq!(response.status(), StatusCode::RANGE_NOT_SATISFIABLE);
+    }
+
+    #[test]
+    fn test_precompressed_sibling() {
+        let response = get("/app.js", &[(header::ACCEPT_ENCODING, "gzip, br")]);
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_ENCODING], "br");
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "text/javascript");
+        assert_eq!(response.headers()[header::CONTENT_LENGTH], "10");
+
+        let response = get("/app.js", &[(header::ACCEPT_ENCODING, "gzip")]);
+        assert!(!response.headers().contains_key(header::CONTENT_ENCODING));
+    }
+}
@@ -0,0 +1,74 @@
+//! Runtime support for assets generated with `dev_reload: true`.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+/// Version of a file on disk, used to detect changes between reads.
+#[derive(PartialEq, Eq)]
+struct FileVersion {
+    modified: Option<SystemTime>,
+    len: u64,
+}
+
+/// Contents of the files read so far, by absolute path.
+static CACHE: Mutex<BTreeMap<&'static str, (FileVersion, &'static [u8])>> =
+    Mutex::new(BTreeMap::new());
+
+/// Read the current contents of an asset file from disk.
+///
+/// The contents are cached until the file's modification time or size changes. Each version of
+/// a file that is read is leaked to hand out `'static` bytes, which is fine for development
+/// builds. Falls back to the embedded bytes when the file cannot be read.
+pub fn read_live(
+    full_path: &'static str,
+    embedded: impl FnOnce() -> &'static [u8],
+) -> &'static [u8] {
+    let Ok(metadata) = fs::metadata(full_path) else {
+        return embedded();
+    };
+    let version = FileVersion {
+        modified: metadata.modified().ok(),
+        len: metadata.len(),
+    };
+
+    let mut cache = CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some((cached_version, bytes)) = cache.get(full_path)
+        && *cached_version == version
+    {
+        return bytes;
+    }
+
+    match fs::read(full_path) {
+        Ok(data) => {
+            let bytes: &'static [u8] = Box::leak(data.into_boxed_slice());
+            cache.insert(full_path, (version, bytes));
+            bytes
+        }
+        Err(_) => embedded(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_live() {
+        let path = std::env::temp_dir().join(format!("asset-traits-dev-{}", std::process::id()));
+        let full_path: &'static str = path.to_string_lossy().into_owned().leak();
+
+        fs::write(full_path, "first").unwrap();
+        let first = read_live(full_path, || b"embedded");
+        assert_eq!(first, b"first");
+        // Unchanged files are served from the cache.
+        assert!(std::ptr::eq(first, read_live(full_path, || b"embedded")));
+
+        fs::write(full_path, "second version").unwrap();
+        assert_eq!(read_live(full_path, || b"embedded"), b"second version");
+
+        fs::remove_file(full_path).unwrap();
+        assert_eq!(read_live(full_path, || b"embedded"), b"embedded");
+    }
+}
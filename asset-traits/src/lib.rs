@@ -1,14 +1,250 @@
 //! Traits for working with compiled assets.
 
+#[doc(hidden)]
+pub mod dev;
+
+#[doc(hidden)]
+pub use phf;
+
+#[cfg(feature = "serde")]
+pub mod serde;
+
+#[cfg(feature = "clap")]
+#[doc(hidden)]
+pub use clap;
+
+/// Implements `Serialize` and `Deserialize` by path for an asset enum generated with
+/// `serde: true`.
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __impl_serde {
+    ($name:ident) => {
+        impl $crate::serde::__private::Serialize for $name {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: $crate::serde::__private::Serializer,
+            {
+                $crate::serde::serialize(self, serializer)
+            }
+        }
+
+        impl<'de> $crate::serde::__private::Deserialize<'de> for $name {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: $crate::serde::__private::Deserializer<'de>,
+            {
+                $crate::serde::deserialize(deserializer)
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "serde"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __impl_serde {
+    ($name:ident) => {
+        ::core::compile_error!("`serde: true` requires the `serde` feature of asset-traits");
+    };
+}
+
+/// Implements `clap::ValueEnum` with the asset paths as possible values for an asset enum
+/// generated with `clap: true`.
+#[cfg(feature = "clap")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __impl_clap {
+    ($name:ident) => {
+        impl $crate::clap::ValueEnum for $name {
+            fn value_variants<'a>() -> &'a [Self] {
+                <Self as $crate::AssetCollection>::all()
+            }
+
+            fn to_possible_value(&self) -> Option<$crate::clap::builder::PossibleValue> {
+                Some($crate::clap::builder::PossibleValue::new(
+                    $crate::Asset::path(self),
+                ))
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "clap"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __impl_clap {
+    ($name:ident) => {
+        ::core::compile_error!("`clap: true` requires the `clap` feature of asset-traits");
+    };
+}
+
+use std::borrow::Cow;
+use std::fmt;
+
+/// Compression algorithm used to store an asset in the binary.
+///
+/// Variants are only available when the matching cargo feature of this crate is enabled.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Compression {
+    /// Gzip (RFC 1952), requires the `gzip` feature.
+    #[cfg(feature = "gzip")]
+    Gzip,
+    /// Brotli (RFC 7932), requires the `brotli` feature.
+    #[cfg(feature = "brotli")]
+    Brotli,
+    /// Zstandard (RFC 8878), requires the `zstd` feature.
+    #[cfg(feature = "zstd")]
+    Zstd,
+}
+
+impl Compression {
+    /// Get the name of the algorithm as used in the HTTP `Content-Encoding` header.
+    pub fn encoding(&self) -> &'static str {
+        match *self {
+            #[cfg(feature = "gzip")]
+            Compression::Gzip => "gzip",
+            #[cfg(feature = "brotli")]
+            Compression::Brotli => "br",
+            #[cfg(feature = "zstd")]
+            Compression::Zstd => "zstd",
+        }
+    }
+
+    /// Decompress bytes that were compressed with this algorithm.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the data is not a valid stream for this algorithm. Data embedded by the
+    /// `assets!` macro is always valid.
+    #[cfg_attr(
+        not(any(feature = "gzip", feature = "brotli", feature = "zstd")),
+        allow(unused_variables)
+    )]
+    pub fn decompress(&self, data: &[u8]) -> Vec<u8> {
+        #[allow(unused_imports)]
+        use std::io::Read;
+
+        match *self {
+            #[cfg(feature = "gzip")]
+            Compression::Gzip => {
+                let mut output = Vec::new();
+                flate2::read::GzDecoder::new(data)
+                    .read_to_end(&mut output)
+                    .expect("Invalid gzip data");
+                output
+            }
+            #[cfg(feature = "brotli")]
+            Compression::Brotli => {
+                let mut output = Vec::new();
+                brotli_decompressor::Decompressor::new(data, 4096)
+                    .read_to_end(&mut output)
+                    .expect("Invalid brotli data");
+                output
+            }
+            #[cfg(feature = "zstd")]
+            Compression::Zstd => zstd::stream::decode_all(data).expect("Invalid zstd data"),
+        }
+    }
+}
+
+/// A directory of assets, generated by the `assets!` macro with `layout: nested`.
+#[derive(Debug)]
+pub struct Directory<A: 'static> {
+    name: &'static str,
+    path: &'static str,
+    files: &'static [A],
+    dirs: &'static [Directory<A>],
+}
+
+impl<A> Directory<A> {
+    #[doc(hidden)]
+    pub const fn new(
+        name: &'static str,
+        path: &'static str,
+        files: &'static [A],
+        dirs: &'static [Directory<A>],
+    ) -> Self {
+        Self {
+            name,
+            path,
+            files,
+            dirs,
+        }
+    }
+
+    /// Get the name of the directory, empty for the asset root.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Get the path of the directory relative to the asset root, empty for the asset root.
+    pub fn path(&self) -> &'static str {
+        self.path
+    }
+
+    /// Get the assets directly inside this directory.
+    pub fn files(&self) -> &'static [A] {
+        self.files
+    }
+
+    /// Get the subdirectories of this directory.
+    pub fn dirs(&self) -> &'static [Directory<A>] {
+        self.dirs
+    }
+
+    /// Find a direct subdirectory by its name.
+    pub fn dir(&self, name: &str) -> Option<&'static Directory<A>> {
+        self.dirs.iter().find(|dir| dir.name == name)
+    }
+}
+
 /// Represents an asset that can be accessed at compile time.
 ///
 /// This trait is implemented by all asset enums generated by the `assets!` macro.
 pub trait Asset {
     /// Get the path of the asset relative to its root directory.
+    ///
+    /// Directories are separated by `/` on every platform.
     fn path(&self) -> &'static str;
 
     /// Get the raw bytes of the asset.
+    ///
+    /// For compressed assets the bytes are decompressed on first access and cached for the
+    /// rest of the program. For assets generated with `dev_reload: true`, debug builds read
+    /// the current contents of the file from disk instead.
     fn bytes(&self) -> &'static [u8];
+
+    /// Get the 32-byte digest of the asset contents, computed at compile time.
+    ///
+    /// The digest is SHA-256 unless the asset enum was generated with `hash: "blake3"`.
+    fn hash(&self) -> &'static [u8; 32];
+
+    /// Get the MIME type of the asset, detected at compile time.
+    ///
+    /// Unknown files are reported as `application/octet-stream`.
+    fn mime_type(&self) -> &'static str;
+
+    /// Get the contents of the asset as text, if they are valid UTF-8.
+    ///
+    /// Files matching the `text` pattern of the `assets!` macro are validated at compile time
+    /// and embedded as `&'static str`.
+    fn text(&self) -> Option<&'static str> {
+        std::str::from_utf8(self.bytes()).ok()
+    }
+
+    /// Get the compression algorithm the asset is stored with, if any.
+    fn compression(&self) -> Option<Compression> {
+        None
+    }
+
+    /// Get the bytes of the asset as stored in the binary, if it is stored compressed.
+    ///
+    /// Use [`Asset::compression`] to find out which algorithm they were compressed with.
+    fn compressed_bytes(&self) -> Option<&'static [u8]> {
+        None
+    }
 }
 
 /// Additional trait for asset collections that can enumerate all available assets.
@@ -17,18 +253,241 @@ where
     Self: 'static,
 {
     /// Get a slice containing all available assets of this type.
+    ///
+    /// Asset enums generated by the `assets!` macro list their assets sorted by path, comparing
+    /// the paths byte by byte with `/` as the separator on every platform. The order, and with
+    /// it the order of the enum variants, does not depend on the file system.
     fn all() -> &'static [Self]
     where
         Self: Sized;
 
     /// Find an asset by its path.
+    ///
+    /// The path is normalized with [`normalize_path`] first, so `ui\\logo.png`,
+    /// `./ui/logo.png` and `ui//icons/../logo.png` all find `ui/logo.png`.
+    ///
+    /// Asset enums generated by the `assets!` macro override this with a lookup in a perfect
+    /// hash map built at compile time.
     fn find_by_path(path: &str) -> Option<Self>
     where
         Self: Sized + Copy,
     {
+        let path = normalize_path(path);
         Self::all()
             .iter()
             .find(|asset| asset.path() == path)
             .copied()
     }
 }
+
+/// Object-safe access to an asset collection.
+///
+/// Unlike [`AssetCollection`], this trait can be used as `&dyn DynAssetCollection`, so
+/// collections of different types can be kept together, e.g. in a plugin registry. Asset enums
+/// generated by the `assets!` macro or `#[derive(Asset)]` provide theirs as the `COLLECTION`
+/// constant.
+pub trait DynAssetCollection: Sync {
+    /// Get the name of the collection, i.e. the name of the asset enum.
+    fn name(&self) -> &'static str;
+
+    /// Get all assets of the collection, in the order of [`AssetCollection::all`].
+    fn entries(&self) -> &'static [&'static dyn Asset];
+
+    /// Find an asset by its path.
+    ///
+    /// The path is normalized with [`normalize_path`] like in
+    /// [`AssetCollection::find_by_path`].
+    fn find(&self, path: &str) -> Option<&'static dyn Asset> {
+        let path = normalize_path(path);
+        self.entries()
+            .iter()
+            .find(|asset| asset.path() == path)
+            .copied()
+    }
+}
+
+/// Normalize a relative asset path to the form returned by [`Asset::path`].
+///
+/// Both `/` and `\\` are accepted as separators, empty and `.` segments are removed and `..`
+/// removes the preceding segment. `..` segments that would leave the root are kept, so such
+/// paths never match an asset.
+pub fn normalize_path(path: &str) -> Cow<'_, str> {
+    let is_normalized = !path.contains('\\')
+        && path
+            .split('/')
+            .all(|segment| !matches!(segment, "" | "." | ".."));
+    if is_normalized {
+        return Cow::Borrowed(path);
+    }
+
+    let mut segments: Vec<&str> = Vec::new();
+    for segment in path.split(['/', '\\']) {
+        match segment {
+            "" | "." => {}
+            ".." if segments.last().is_some_and(|last| *last != "..") => {
+                segments.pop();
+            }
+            segment => segments.push(segment),
+        }
+    }
+    Cow::Owned(segments.join("/"))
+}
+
+/// Error returned when parsing an asset from a path that does not belong to the collection.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownAssetError {
+    path: String,
+}
+
+impl UnknownAssetError {
+    /// Create an error for the requested path.
+    pub fn new(path: impl Into<String>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// The path that was requested.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+}
+
+impl fmt::Display for UnknownAssetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown asset path '{}'", self.path)
+    }
+}
+
+impl std::error::Error for UnknownAssetError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_path() {
+        assert!(matches!(
+            normalize_path("ui/logo.png"),
+            Cow::Borrowed("ui/logo.png")
+        ));
+        assert_eq!(normalize_path("ui\\logo.png"), "ui/logo.png");
+        assert_eq!(normalize_path("./ui/logo.png"), "ui/logo.png");
+        assert_eq!(normalize_path("/ui//logo.png"), "ui/logo.png");
+        assert_eq!(normalize_path("ui/icons/../logo.png"), "ui/logo.png");
+        assert_eq!(normalize_path("../logo.png"), "../logo.png");
+    }
+
+    struct Readme;
+
+    impl Asset for Readme {
+        fn path(&self) -> &'static str {
+            "docs/README.md"
+        }
+
+        fn bytes(&self) -> &'static [u8] {
+            b"# Assets"
+        }
+
+        fn hash(&self) -> &'static [u8; 32] {
+            &[0; 32]
+        }
+
+        fn mime_type(&self) -> &'static str {
+            "text/markdown"
+        }
+    }
+
+    struct Docs;
+
+    impl DynAssetCollection for Docs {
+        fn name(&self) -> &'static str {
+            "Docs"
+        }
+
+        fn entries(&self) -> &'static [&'static dyn Asset] {
+            &[&Readme]
+        }
+    }
+
+    #[test]
+    fn test_dyn_asset_collection() {
+        let collection: &dyn DynAssetCollection = &Docs;
+        assert_eq!(collection.entries().len(), 1);
+        let readme = collection.find("./docs\\README.md").unwrap();
+        assert_eq!(readme.text(), Some("# Assets"));
+        assert!(collection.find("README.md").is_none());
+    }
+
+    #[cfg(feature = "clap")]
+    #[test]
+    fn test_impl_clap() {
+        use clap::ValueEnum;
+
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        enum Templates {
+            Welcome,
+            Reset,
+        }
+
+        impl Asset for Templates {
+            fn path(&self) -> &'static str {
+                match self {
+                    Templates::Welcome => "welcome.html",
+                    Templates::Reset => "auth/reset.html",
+                }
+            }
+
+            fn bytes(&self) -> &'static [u8] {
+                b""
+            }
+
+            fn hash(&self) -> &'static [u8; 32] {
+                &[0; 32]
+            }
+
+            fn mime_type(&self) -> &'static str {
+                "text/html"
+            }
+        }
+
+        impl AssetCollection for Templates {
+            fn all() -> &'static [Self] {
+                &[Templates::Reset, Templates::Welcome]
+            }
+        }
+
+        crate::__impl_clap!(Templates);
+
+        assert_eq!(
+            Templates::value_variants(),
+            [Templates::Reset, Templates::Welcome]
+        );
+        let value = Templates::Reset.to_possible_value().unwrap();
+        assert_eq!(value.get_name(), "auth/reset.html");
+
+        let command = clap::Command::new("mailer").arg(
+            clap::Arg::new("template")
+                .long("template")
+                .value_parser(clap::builder::EnumValueParser::<Templates>::new()),
+        );
+        let matches = command
+            .clone()
+            .try_get_matches_from(["mailer", "--template", "welcome.html"])
+            .unwrap();
+        assert_eq!(
+            matches.get_one::<Templates>("template"),
+            Some(&Templates::Welcome)
+        );
+        assert!(
+            command
+                .try_get_matches_from(["mailer", "--template", "missing.html"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_unknown_asset_error() {
+        let error = UnknownAssetError::new("ui/missing.png");
+        assert_eq!(error.path(), "ui/missing.png");
+        assert_eq!(error.to_string(), "unknown asset path 'ui/missing.png'");
+    }
+}
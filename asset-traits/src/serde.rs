@@ -0,0 +1,180 @@
+//! Serialization of assets as their path, enabled by the `serde` feature.
+//!
+//! Enums generated by the `assets!` macro with `serde: true` implement `Serialize` and
+//! `Deserialize` with these functions. For other asset enums, they can be used on fields with
+//! `#[serde(with = "asset_traits::serde")]`.
+
+use std::fmt;
+use std::marker::PhantomData;
+
+use ::serde::de::{self, Deserializer, Visitor};
+use ::serde::ser::Serializer;
+
+use crate::{Asset, AssetCollection, UnknownAssetError};
+
+#[doc(hidden)]
+pub mod __private {
+    pub use ::serde::{Deserialize, Deserializer, Serialize, Serializer};
+}
+
+/// Maximum number of close matches listed when a path is unknown.
+const MAX_SUGGESTIONS: usize = 3;
+
+/// Serialize an asset as its path.
+pub fn serialize<A, S>(asset: &A, serializer: S) -> Result<S::Ok, S::Error>
+where
+    A: Asset,
+    S: Serializer,
+{
+    serializer.serialize_str(asset.path())
+}
+
+/// Deserialize an asset from its path with [`AssetCollection::find_by_path`].
+///
+/// Unknown paths are an error that lists the closest known paths.
+pub fn deserialize<'de, C, D>(deserializer: D) -> Result<C, D::Error>
+where
+    C: AssetCollection + Copy,
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_str(PathVisitor(PhantomData))
+}
+
+struct PathVisitor<C>(PhantomData<fn() -> C>);
+
+impl<C> Visitor<'_> for PathVisitor<C>
+where
+    C: AssetCollection + Copy,
+{
+    type Value = C;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("an asset path")
+    }
+
+    fn visit_str<E: de::Error>(self, path: &str) -> Result<C, E> {
+        C::find_by_path(path).ok_or_else(|| {
+            let suggestions = similar_paths::<C>(path);
+            let mut message = UnknownAssetError::new(path).to_string();
+            if !suggestions.is_empty() {
+                message.push_str(&format!(", did you mean '{}'?", suggestions.join("', '")));
+            }
+            E::custom(message)
+        })
+    }
+}
+
+/// Find the paths of `C` that are closest to `path`, best match first.
+fn similar_paths<C: AssetCollection>(path: &str) -> Vec<&'static str> {
+    let max_distance = (path.chars().count() / 3).max(2);
+    let mut candidates: Vec<_> = C::all()
+        .iter()
+        .map(|asset| (edit_distance(path, asset.path()), asset.path()))
+        .filter(|&(distance, _)| distance <= max_distance)
+        .collect();
+    candidates.sort();
+    candidates
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, path)| path)
+        .collect()
+}
+
+/// Levenshtein distance between two strings, counted in characters.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, a_char) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, &b_char) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(a_char != b_char);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+
+    row[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::serde::de::value::{Error as ValueError, StrDeserializer};
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum TestAssets {
+        Logo,
+        Icon,
+    }
+
+    impl Asset for TestAssets {
+        fn path(&self) -> &'static str {
+            match self {
+                TestAssets::Logo => "ui/logo.png",
+                TestAssets::Icon => "ui/icon.png",
+            }
+        }
+
+        fn bytes(&self) -> &'static [u8] {
+            b""
+        }
+
+        fn hash(&self) -> &'static [u8; 32] {
+            &[0; 32]
+        }
+
+        fn mime_type(&self) -> &'static str {
+            "image/png"
+        }
+    }
+
+    impl AssetCollection for TestAssets {
+        fn all() -> &'static [Self] {
+            &[TestAssets::Logo, TestAssets::Icon]
+        }
+    }
+
+    crate::__impl_serde!(TestAssets);
+
+    fn from_str(path: &str) -> Result<TestAssets, ValueError> {
+        deserialize(StrDeserializer::<ValueError>::new(path))
+    }
+
+    #[test]
+    fn test_deserialize() {
+        assert_eq!(from_str("ui/logo.png").unwrap(), TestAssets::Logo);
+        assert_eq!(from_str("./ui\\icon.png").unwrap(), TestAssets::Icon);
+    }
+
+    #[test]
+    fn test_impl_serde() {
+        use ::serde::Deserialize;
+
+        let deserializer = StrDeserializer::<ValueError>::new("ui\\logo.png");
+        assert_eq!(
+            TestAssets::deserialize(deserializer).unwrap(),
+            TestAssets::Logo
+        );
+    }
+
+    #[test]
+    fn test_unknown_path() {
+        assert_eq!(
+            from_str("ui/logo.jpg").unwrap_err().to_string(),
+            "unknown asset path 'ui/logo.jpg', did you mean 'ui/logo.png'?"
+        );
+        assert_eq!(
+            from_str("audio/theme.ogg").unwrap_err().to_string(),
+            "unknown asset path 'audio/theme.ogg'"
+        );
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("logo.png", "logo.png"), 0);
+    }
+}
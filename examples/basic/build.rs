@@ -0,0 +1,3 @@
+fn main() {
+    asset_build::track("assets").unwrap();
+}
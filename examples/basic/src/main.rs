@@ -4,13 +4,29 @@ use asset_traits::{Asset, AssetCollection};
 // Generate asset enums for different directories
 assets!(AudioAssets, "assets/audio");
 assets!(UiAssets, "assets/ui", include: r"\.(png|jpg|svg)$");
-assets!(ConfigAssets, "assets/config", include: r"\.json$", ignore: r"temp");
+assets!(
+    ConfigAssets,
+    "assets/config",
+    include: r"\.json$",
+    ignore_glob: ["temp", "*.tmp"],
+    compress: "gzip",
+    text: r"\.json$"
+);
 
 // Function that works with any asset type
 fn process_asset<T: Asset>(asset: T) {
     let path = asset.path();
     let data = asset.bytes();
-    println!("Processing asset: {} ({} bytes)", path, data.len());
+    let hash: String = asset.hash()[..8]
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+    println!(
+        "Processing asset: {} ({} bytes, hash {})",
+        path,
+        data.len(),
+        hash
+    );
 }
 
 // Function that takes a specific asset type
@@ -25,7 +41,18 @@ fn main() {
 
     // Find an asset by path
     if let Some(config) = ConfigAssets::find_by_path("settings.json") {
+        if let Some(compressed) = config.compressed_bytes() {
+            println!(
+                "Config {} is stored compressed ({} bytes)",
+                config.path(),
+                compressed.len()
+            );
+        }
         process_asset(config);
+
+        if let Some(text) = config.text() {
+            println!("{} starts with {:?}", config.path(), &text[..1]);
+        }
     }
 
     // Iterate over all assets of a type
@@ -36,6 +63,11 @@ fn main() {
     // Print information about all UI assets
     println!("UI Assets:");
     for asset in UiAssets::all() {
-        println!("  - {}: {} bytes", asset.path(), asset.bytes().len());
+        println!(
+            "  - {} ({}): {} bytes",
+            asset.path(),
+            asset.mime_type(),
+            asset.bytes().len()
+        );
     }
 }